@@ -0,0 +1,200 @@
+//! Cycle detection for sequences `start, step(start), step(step(start)), ...`
+//! over a finite state space, such as a platform spun again and again or a
+//! walk through a map that repeats its directions.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// Step `prefix` is the first state that comes back, `period` steps later.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state equals the state at step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+
+    /// State at step `n`, stepping at most `prefix + period - 1` times.
+    pub fn state_at<S: Clone>(&self, start: &S, step: impl FnMut(&mut S), n: usize) -> S {
+        advance(start, step, self.reduce(n))
+    }
+}
+
+fn advance<S: Clone>(start: &S, mut step: impl FnMut(&mut S), n: usize) -> S {
+    let mut state = start.clone();
+    for _ in 0..n {
+        step(&mut state);
+    }
+    state
+}
+
+/// Brent's algorithm: only two states are kept and compared. Loops forever if
+/// the sequence never repeats.
+pub fn brent<S: Clone + PartialEq>(start: &S, mut step: impl FnMut(&mut S)) -> Cycle {
+    // Find the period: the tortoise waits at powers of two for the hare to come round.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    step(&mut hare);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        step(&mut hare);
+        period += 1;
+    }
+
+    // With the hare `period` steps ahead, they first meet at the start of the cycle.
+    let mut tortoise = start.clone();
+    let mut hare = advance(start, &mut step, period);
+    let mut prefix = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+fn fingerprint<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Fingerprints states until one comes back or `limit` steps have been
+/// taken, leaving `state` at the last step taken.
+fn search<S: Clone + Hash + Eq>(
+    start: &S,
+    mut step: impl FnMut(&mut S),
+    state: &mut S,
+    limit: usize,
+) -> Option<Cycle> {
+    let mut seen = HashMap::<u64, Vec<usize>>::new();
+
+    for i in 0..limit {
+        let steps = seen.entry(fingerprint(state)).or_default();
+        let repeat = steps
+            .iter()
+            .find(|&&j| advance(start, &mut step, j) == *state);
+        if let Some(&prefix) = repeat {
+            return Some(Cycle {
+                prefix,
+                period: i - prefix,
+            });
+        }
+        steps.push(i);
+        step(state);
+    }
+
+    None
+}
+
+/// Remembers a 64-bit fingerprint of every state instead of the state itself
+/// and stops at the first repeat, so it steps `prefix + period` times. A
+/// matching fingerprint is confirmed by replaying the earlier state from
+/// `start`, so a hash collision can't give a wrong answer.
+pub fn hashed<S: Clone + Hash + Eq>(start: &S, step: impl FnMut(&mut S)) -> Cycle {
+    search(start, step, &mut start.clone(), usize::MAX).unwrap()
+}
+
+/// State at step `n`, skipping ahead if a state repeats before then.
+pub fn nth<S: Clone + Hash + Eq>(start: &S, mut step: impl FnMut(&mut S), n: usize) -> S {
+    let mut state = start.clone();
+    match search(start, &mut step, &mut state, n) {
+        Some(cycle) => cycle.state_at(start, step, n),
+        None => state,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, hashed, nth, Cycle};
+    use std::{collections::HashMap, hash::Hash};
+
+    fn naive<S: Clone + Hash + Eq>(start: &S, mut step: impl FnMut(&mut S)) -> Cycle {
+        let mut seen = HashMap::new();
+        let mut state = start.clone();
+        for i in 0.. {
+            if let Some(prefix) = seen.insert(state.clone(), i) {
+                return Cycle {
+                    prefix,
+                    period: i - prefix,
+                };
+            }
+            step(&mut state);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn matches_naive() {
+        let step = |x: &mut u64| *x = (*x * *x + 1) % 1009;
+        for start in 0..1009 {
+            let expected = naive(&start, step);
+            assert_eq!(brent(&start, step), expected, "{start}");
+            assert_eq!(hashed(&start, step), expected, "{start}");
+        }
+    }
+
+    #[test]
+    fn pure_cycle() {
+        let step = |x: &mut u8| *x = (*x + 1) % 7;
+        let expected = Cycle {
+            prefix: 0,
+            period: 7,
+        };
+        assert_eq!(brent(&3, step), expected);
+        assert_eq!(hashed(&3, step), expected);
+        let expected = Cycle {
+            prefix: 0,
+            period: 1,
+        };
+        assert_eq!(brent(&5, |_: &mut u8| {}), expected);
+        assert_eq!(hashed(&5, |_: &mut u8| {}), expected);
+    }
+
+    #[test]
+    fn state_at() {
+        // 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+        let step = |x: &mut u32| {
+            *x = if *x == 1 {
+                4
+            } else if x.is_multiple_of(2) {
+                *x / 2
+            } else {
+                3 * *x + 1
+            }
+        };
+        let cycle = brent(&10, step);
+        assert_eq!(hashed(&10, step), cycle);
+        assert_eq!(
+            cycle,
+            Cycle {
+                prefix: 4,
+                period: 3
+            }
+        );
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(1_000_000_001), 5);
+        assert_eq!(cycle.state_at(&10, step, 1), 5);
+        assert_eq!(cycle.state_at(&10, step, 1_000_000_001), 2);
+        assert_eq!(nth(&10, step, 1_000_000_001), 2);
+        assert_eq!(nth(&10, step, 3), 8);
+        assert_eq!(nth(&10, step, 0), 10);
+    }
+}
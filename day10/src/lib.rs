@@ -0,0 +1,615 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Symbol {
+    visit: bool,
+    pub top: bool,
+    pub bottom: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl From<char> for Symbol {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => Self {
+                visit: false,
+                top: false,
+                bottom: false,
+                left: false,
+                right: false,
+            },
+            '|' => Self {
+                visit: false,
+                top: true,
+                bottom: true,
+                left: false,
+                right: false,
+            },
+            '-' => Self {
+                visit: false,
+                top: false,
+                bottom: false,
+                left: true,
+                right: true,
+            },
+            'L' => Self {
+                visit: false,
+                top: true,
+                bottom: false,
+                left: false,
+                right: true,
+            },
+            'J' => Self {
+                visit: false,
+                top: true,
+                bottom: false,
+                left: true,
+                right: false,
+            },
+            '7' => Self {
+                visit: false,
+                top: false,
+                bottom: true,
+                left: true,
+                right: false,
+            },
+            'F' => Self {
+                visit: false,
+                top: false,
+                bottom: true,
+                left: false,
+                right: true,
+            },
+            _ => panic!(),
+        }
+    }
+}
+
+fn spread(map: &Map) -> (usize, usize, Vec<Vec<char>>) {
+    let (rows, cols) = (map.rows(), map.cols());
+
+    let mut spread = vec![vec!['.'; cols * 2 + 1]; rows * 2 + 1];
+
+    for (rown, row) in map.tiles.iter().enumerate() {
+        for (coln, elem) in row.iter().enumerate() {
+            spread[rown * 2 + 1][coln * 2 + 1] = *elem;
+        }
+    }
+    for i in 0..rows - 1 {
+        let rown = i * 2 + 2;
+        for j in 0..cols {
+            let coln = j * 2 + 1;
+            if Symbol::from(spread[rown - 1][coln]).bottom
+                && Symbol::from(spread[rown + 1][coln]).top
+            {
+                spread[rown][coln] = '|';
+            }
+        }
+    }
+
+    for j in 0..cols - 1 {
+        let coln = j * 2 + 2;
+        for i in 0..rows {
+            let rown = i * 2 + 1;
+            if Symbol::from(spread[rown][coln - 1]).right
+                && Symbol::from(spread[rown][coln + 1]).left
+            {
+                spread[rown][coln] = '-';
+            }
+        }
+    }
+
+    (rows, cols, spread)
+}
+
+fn enclosed_spread(map: &Map) -> usize {
+    let (irows, icols, spreaded) = spread(map);
+
+    let row = spreaded.first().unwrap().len();
+    let col = spreaded.len();
+    let start = (map.start.0 * 2 + 1, map.start.1 * 2 + 1);
+
+    let mut map = spreaded
+        .into_iter()
+        .map(|row| row.into_iter().map(Symbol::from).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    map[start.0][start.1].visit = true;
+    let mut starting = vec![start];
+
+    while !starting.is_empty() {
+        let mut new_pos = vec![];
+        for elem in starting {
+            if (0..col).contains(&((elem.0 as i32 - 1) as usize))
+                && (0..row).contains(&elem.1)
+                && map[elem.0][elem.1].top
+                && map[elem.0 - 1][elem.1].bottom
+                && !map[elem.0 - 1][elem.1].visit
+            {
+                new_pos.push((elem.0 - 1, elem.1));
+                map[elem.0 - 1][elem.1].visit = true;
+            }
+            if (0..col).contains(&(elem.0 + 1))
+                && (0..row).contains(&elem.1)
+                && map[elem.0][elem.1].bottom
+                && map[elem.0 + 1][elem.1].top
+                && !map[elem.0 + 1][elem.1].visit
+            {
+                new_pos.push((elem.0 + 1, elem.1));
+                map[elem.0 + 1][elem.1].visit = true;
+            }
+            if (0..col).contains(&elem.0)
+                && (0..row).contains(&((elem.1 as i32 - 1) as usize))
+                && map[elem.0][elem.1].left
+                && map[elem.0][elem.1 - 1].right
+                && !map[elem.0][elem.1 - 1].visit
+            {
+                new_pos.push((elem.0, elem.1 - 1));
+                map[elem.0][elem.1 - 1].visit = true;
+            }
+            if (0..col).contains(&elem.0)
+                && (0..row).contains(&(elem.1 + 1))
+                && map[elem.0][elem.1].right
+                && map[elem.0][elem.1 + 1].left
+                && !map[elem.0][elem.1 + 1].visit
+            {
+                new_pos.push((elem.0, elem.1 + 1));
+                map[elem.0][elem.1 + 1].visit = true;
+            }
+        }
+        starting = new_pos;
+    }
+
+    let mut field = map
+        .into_iter()
+        .map(|line| line.into_iter().map(|v| v.visit).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let row = field.first().unwrap().len();
+    let col = field.len();
+
+    let mut points = vec![(0, 0)];
+
+    while !points.is_empty() {
+        let mut points2 = vec![];
+
+        for point in points {
+            if (0..col).contains(&((point.0 as i32 - 1) as usize))
+                && (0..row).contains(&point.1)
+                && !field[point.0 - 1][point.1]
+            {
+                field[point.0 - 1][point.1] = true;
+                points2.push((point.0 - 1, point.1));
+            }
+            if (0..col).contains(&point.0)
+                && (0..row).contains(&((point.1 as i32 - 1) as usize))
+                && !field[point.0][point.1 - 1]
+            {
+                field[point.0][point.1 - 1] = true;
+                points2.push((point.0, point.1 - 1));
+            }
+            if (0..col).contains(&(point.0 + 1))
+                && (0..row).contains(&point.1)
+                && !field[point.0 + 1][point.1]
+            {
+                field[point.0 + 1][point.1] = true;
+                points2.push((point.0 + 1, point.1));
+            }
+            if (0..col).contains(&point.0)
+                && (0..row).contains(&(point.1 + 1))
+                && !field[point.0][point.1 + 1]
+            {
+                field[point.0][point.1 + 1] = true;
+                points2.push((point.0, point.1 + 1));
+            }
+        }
+        points = points2;
+    }
+
+    let mut ctr = 0;
+
+    for i in 0..irows {
+        let row = i * 2 + 1;
+        for j in 0..icols {
+            let col = j * 2 + 1;
+            if !field[row][col] {
+                ctr += 1;
+            }
+        }
+    }
+
+    ctr
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Dir::Up => Dir::Down,
+            Dir::Down => Dir::Up,
+            Dir::Left => Dir::Right,
+            Dir::Right => Dir::Left,
+        }
+    }
+
+    fn step(self, (row, col): (usize, usize), rows: usize, cols: usize) -> Option<(usize, usize)> {
+        match self {
+            Dir::Up if row > 0 => Some((row - 1, col)),
+            Dir::Down if row + 1 < rows => Some((row + 1, col)),
+            Dir::Left if col > 0 => Some((row, col - 1)),
+            Dir::Right if col + 1 < cols => Some((row, col + 1)),
+            _ => None,
+        }
+    }
+}
+
+impl Symbol {
+    pub fn connects(&self, dir: Dir) -> bool {
+        match dir {
+            Dir::Up => self.top,
+            Dir::Down => self.bottom,
+            Dir::Left => self.left,
+            Dir::Right => self.right,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    NoStart,
+    /// The start tile needs exactly two neighbours pointing back at it.
+    StartConnections(usize),
+    /// The loop from the start leaves this pipe through an opening that leads
+    /// off the map or into a tile that doesn't connect back.
+    BrokenLoop((usize, usize)),
+}
+
+/// Pipe whose two openings point in the given directions.
+fn pipe(a: Dir, b: Dir) -> char {
+    match (a, b) {
+        (Dir::Up, Dir::Down) => '|',
+        (Dir::Left, Dir::Right) => '-',
+        (Dir::Up, Dir::Right) => 'L',
+        (Dir::Up, Dir::Left) => 'J',
+        (Dir::Down, Dir::Left) => '7',
+        (Dir::Down, Dir::Right) => 'F',
+        _ => pipe(b, a),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Map {
+    /// Tiles with `S` already replaced by its real pipe.
+    pub tiles: Vec<Vec<char>>,
+    pub start: (usize, usize),
+    /// The main loop in walking order, starting with `S`.
+    path: Vec<(usize, usize)>,
+}
+
+impl Map {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut tiles = input
+            .lines()
+            .map(|line| line.trim().chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let start = tiles
+            .iter()
+            .enumerate()
+            .find_map(|(row, line)| line.iter().position(|ch| *ch == 'S').map(|col| (row, col)))
+            .ok_or(Error::NoStart)?;
+        tiles[start.0][start.1] = '.';
+
+        let mut map = Self {
+            tiles,
+            start,
+            path: vec![],
+        };
+        let dirs = Dir::ALL
+            .into_iter()
+            .filter(|dir| {
+                map.neighbour(start, *dir).is_some_and(|(row, col)| {
+                    Symbol::from(map.tiles[row][col]).connects(dir.opposite())
+                })
+            })
+            .collect::<Vec<_>>();
+        let [a, b] = dirs[..] else {
+            return Err(Error::StartConnections(dirs.len()));
+        };
+        map.tiles[start.0][start.1] = pipe(a, b);
+        map.path = map.walk()?;
+
+        Ok(map)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.tiles.first().unwrap().len()
+    }
+
+    fn neighbour(&self, pos: (usize, usize), dir: Dir) -> Option<(usize, usize)> {
+        dir.step(pos, self.rows(), self.cols())
+    }
+
+    fn symbol(&self, (row, col): (usize, usize)) -> Symbol {
+        Symbol::from(self.tiles[row][col])
+    }
+
+    /// Follows the pipes from `S` until they lead back to it.
+    fn walk(&self) -> Result<Vec<(usize, usize)>, Error> {
+        let mut path = vec![self.start];
+        let mut pos = self.start;
+        let mut dir = Dir::ALL
+            .into_iter()
+            .find(|dir| self.symbol(pos).connects(*dir))
+            .unwrap();
+
+        loop {
+            let next = self
+                .neighbour(pos, dir)
+                .filter(|next| self.symbol(*next).connects(dir.opposite()))
+                .ok_or(Error::BrokenLoop(pos))?;
+            if next == self.start {
+                return Ok(path);
+            }
+            let sym = self.symbol(next);
+            path.push(next);
+            pos = next;
+            dir = Dir::ALL
+                .into_iter()
+                .find(|d| *d != dir.opposite() && sym.connects(*d))
+                .unwrap();
+        }
+    }
+
+    /// Tiles of the main loop in walking order, starting with `S`.
+    pub fn trace(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+}
+
+/// Counts enclosed tiles from the loop alone: the shoelace formula gives the area
+/// and Pick's theorem turns it into the number of interior lattice points.
+fn enclosed_shoelace(map: &Map) -> usize {
+    let path = map.trace();
+
+    let twice_area = path
+        .iter()
+        .zip(path.iter().cycle().skip(1))
+        .map(|(a, b)| (a.0 * b.1) as i64 - (b.0 * a.1) as i64)
+        .sum::<i64>()
+        .unsigned_abs() as usize;
+
+    // Pick: 2A = 2i + b - 2, and 2A - b is -2 for the smallest loop, so add 2 first.
+    (twice_area + 2 - path.len()) / 2
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Algorithm {
+    /// Flood fill over a grid spread to twice the size, so gaps between pipes become tiles.
+    Spread,
+    /// Shoelace formula and Pick's theorem over the traced loop.
+    Shoelace,
+}
+
+pub fn enclosed(map: &Map, algorithm: Algorithm) -> usize {
+    match algorithm {
+        Algorithm::Spread => enclosed_spread(map),
+        Algorithm::Shoelace => enclosed_shoelace(map),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+impl Map {
+    /// Sorts every tile into the loop, inside or outside. A scan along each row
+    /// flips between outside and inside whenever it crosses a loop tile that
+    /// opens upwards.
+    pub fn regions(&self) -> Vec<Vec<Region>> {
+        let mut regions = vec![vec![Region::Outside; self.cols()]; self.rows()];
+        for &pos in self.trace() {
+            regions[pos.0][pos.1] = Region::Loop;
+        }
+
+        for (row, line) in regions.iter_mut().enumerate() {
+            let mut inside = false;
+            for (col, region) in line.iter_mut().enumerate() {
+                if *region == Region::Loop {
+                    inside ^= self.symbol((row, col)).top;
+                } else if inside {
+                    *region = Region::Inside;
+                }
+            }
+        }
+
+        regions
+    }
+}
+
+fn box_drawing(tile: char) -> char {
+    match tile {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => ' ',
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Style {
+    /// Loop in box-drawing characters, `I` for inside and `O` for outside tiles.
+    Plain,
+    /// Like `Plain`, but coloured, and junk pipes stay visible as dimmed pipes.
+    Ansi,
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[32m";
+const BLUE: &str = "\x1b[34m";
+
+pub fn render(map: &Map, style: Style) -> String {
+    let regions = map.regions();
+    let mut res = String::new();
+
+    for (row, line) in regions.iter().enumerate() {
+        for (col, region) in line.iter().enumerate() {
+            let tile = map.tiles[row][col];
+            let mark = match region {
+                Region::Loop => box_drawing(tile),
+                Region::Inside => 'I',
+                Region::Outside => 'O',
+            };
+            match style {
+                Style::Plain => res.push(mark),
+                Style::Ansi => {
+                    let colour = match region {
+                        Region::Loop => BOLD,
+                        Region::Inside => GREEN,
+                        Region::Outside => BLUE,
+                    };
+                    res.push_str(colour);
+                    if *region != Region::Loop && tile != '.' {
+                        res.push_str(DIM);
+                        res.push(box_drawing(tile));
+                    } else {
+                        res.push(mark);
+                    }
+                    res.push_str(RESET);
+                }
+            }
+        }
+        res.push('\n');
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enclosed, render, Algorithm, Error, Map, Region, Style};
+
+    #[test]
+    fn infers_start() {
+        let map = Map::parse(
+            "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        )
+        .unwrap();
+        assert_eq!(map.start, (1, 1));
+        assert_eq!(map.tiles[1][1], 'F');
+
+        let map = Map::parse(
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        )
+        .unwrap();
+        assert_eq!(map.tiles[0][4], '7');
+    }
+
+    #[test]
+    fn start_errors() {
+        assert_eq!(Map::parse("...\n.-.\n..."), Err(Error::NoStart));
+        assert_eq!(Map::parse("...\n.S.\n..."), Err(Error::StartConnections(0)));
+        assert_eq!(Map::parse(".|.\n-S-\n.|."), Err(Error::StartConnections(4)));
+        assert_eq!(Map::parse(".|.\n-S.\n.|."), Err(Error::StartConnections(3)));
+        assert_eq!(Map::parse("S-.\n|..\n..."), Err(Error::BrokenLoop((1, 0))));
+        assert_eq!(Map::parse("S-\n|."), Err(Error::BrokenLoop((1, 0))));
+        assert_eq!(Map::parse("S7\n|J"), Err(Error::BrokenLoop((1, 0))));
+    }
+
+    #[test]
+    fn regions_match_enclosed() {
+        let map = Map::parse(
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        )
+        .unwrap();
+        let inside = map
+            .regions()
+            .iter()
+            .flatten()
+            .filter(|region| **region == Region::Inside)
+            .count();
+        assert_eq!(inside, 10);
+        assert_eq!(inside, enclosed(&map, Algorithm::Spread));
+    }
+
+    #[test]
+    fn render_plain() {
+        let map = Map::parse(
+            "..........
+.S------7.
+.|F----7|.
+.||-...||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        )
+        .unwrap();
+        assert_eq!(
+            render(&map, Style::Plain),
+            "OOOOOOOOOO
+O┌──────┐O
+O│┌────┐│O
+O││OOOO││O
+O││OOOO││O
+O│└─┐┌─┘│O
+O│II││II│O
+O└──┘└──┘O
+OOOOOOOOOO
+"
+        );
+    }
+
+    #[test]
+    fn render_ansi_dims_junk() {
+        let map = Map::parse("S7.\n|L7\nL-J").unwrap();
+        let res = render(&map, Style::Ansi);
+        assert!(res.starts_with("\x1b[1m┌\x1b[0m\x1b[1m┐\x1b[0m\x1b[34mO\x1b[0m\n"));
+        let map = Map::parse("S-7\n|-|\nL-J").unwrap();
+        let res = render(&map, Style::Ansi);
+        assert!(res.contains("\x1b[32m\x1b[2m─\x1b[0m"));
+    }
+}
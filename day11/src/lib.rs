@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+
+/// Galaxy positions as `(row, col)`, in reading order.
+pub fn galaxies(input: &str) -> Vec<(usize, usize)> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.trim()
+                .chars()
+                .enumerate()
+                .filter_map(move |(j, ch)| match ch {
+                    '#' => Some((i, j)),
+                    '.' => None,
+                    _ => panic!(),
+                })
+        })
+        .collect()
+}
+
+/// Moves coordinates along one axis as if every line without a galaxy were
+/// `factor` lines wide. A coordinate has `coord - occupied` empty lines before
+/// it, where `occupied` counts the distinct coordinates below it.
+fn expand(coords: &[usize], factor: usize) -> Vec<usize> {
+    let mut occupied = coords.to_vec();
+    occupied.sort_unstable();
+    occupied.dedup();
+
+    coords
+        .iter()
+        .map(|&coord| {
+            let below = occupied.partition_point(|&other| other < coord);
+            coord + (coord - below) * (factor - 1)
+        })
+        .collect()
+}
+
+/// Sum of `|a - b|` over all pairs, in one pass over sorted coordinates:
+/// the i-th coordinate is bigger than the i coordinates before it.
+fn pairwise_sum(sorted: &[usize]) -> usize {
+    let mut prefix = 0;
+
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &coord)| {
+            let res = coord * i - prefix;
+            prefix += coord;
+            res
+        })
+        .sum()
+}
+
+/// Galaxies at their expanded positions, numbered in reading order from 0.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Universe {
+    galaxies: Vec<(usize, usize)>,
+}
+
+impl Universe {
+    pub fn new(input: &str, row_factor: usize, col_factor: usize) -> Self {
+        let galaxies = galaxies(input);
+
+        let rows = galaxies.iter().map(|(row, _)| *row).collect::<Vec<_>>();
+        let cols = galaxies.iter().map(|(_, col)| *col).collect::<Vec<_>>();
+        let galaxies = expand(&rows, row_factor)
+            .into_iter()
+            .zip(expand(&cols, col_factor))
+            .collect();
+
+        Self { galaxies }
+    }
+
+    pub fn len(&self) -> usize {
+        self.galaxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.galaxies.is_empty()
+    }
+
+    pub fn galaxy(&self, i: usize) -> (usize, usize) {
+        self.galaxies[i]
+    }
+
+    pub fn distance(&self, i: usize, j: usize) -> usize {
+        let (a, b) = (self.galaxies[i], self.galaxies[j]);
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+
+    /// Closest other galaxy and its distance; ties go to the lower number.
+    pub fn nearest(&self, i: usize) -> Option<(usize, usize)> {
+        (0..self.len())
+            .filter(|&j| j != i)
+            .map(|j| (j, self.distance(i, j)))
+            .min_by_key(|&(j, dist)| (dist, j))
+    }
+
+    /// Number of galaxy pairs at each distance.
+    pub fn histogram(&self) -> BTreeMap<usize, usize> {
+        let mut res = BTreeMap::new();
+        for i in 0..self.len() {
+            for j in i + 1..self.len() {
+                *res.entry(self.distance(i, j)).or_insert(0) += 1;
+            }
+        }
+        res
+    }
+
+    /// Sum of distances over all pairs, without visiting every pair.
+    pub fn total(&self) -> usize {
+        let mut rows = self
+            .galaxies
+            .iter()
+            .map(|(row, _)| *row)
+            .collect::<Vec<_>>();
+        let mut cols = self
+            .galaxies
+            .iter()
+            .map(|(_, col)| *col)
+            .collect::<Vec<_>>();
+        rows.sort_unstable();
+        cols.sort_unstable();
+
+        pairwise_sum(&rows) + pairwise_sum(&cols)
+    }
+}
+
+pub fn solution(input: &str, factor: usize) -> usize {
+    Universe::new(input, factor, factor).total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{galaxies, solution, Universe};
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    fn brute_force(input: &str, factor: usize) -> usize {
+        let galaxies = galaxies(input);
+        let empty_rows = (0..input.lines().count())
+            .filter(|row| galaxies.iter().all(|g| g.0 != *row))
+            .collect::<Vec<_>>();
+        let cols = input.lines().next().unwrap().len();
+        let empty_cols = (0..cols)
+            .filter(|col| galaxies.iter().all(|g| g.1 != *col))
+            .collect::<Vec<_>>();
+        let dist = |a: usize, b: usize, empty: &[usize]| {
+            let (a, b) = (a.min(b), a.max(b));
+            b - a + empty.iter().filter(|e| (a..b).contains(*e)).count() * (factor - 1)
+        };
+
+        let mut res = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                res += dist(a.0, b.0, &empty_rows) + dist(a.1, b.1, &empty_cols);
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let inputs = [
+            EXAMPLE,
+            // Wide, with several empty columns in a row and galaxies sharing a column.
+            "#.....#....#\n............\n..#.......#.\n#...........",
+            // Tall, with empty rows at both ends.
+            "...\n.#.\n...\n...\n#..\n..#\n#..\n...",
+            "#",
+            "....\n.#..\n....",
+        ];
+        for input in inputs {
+            for factor in [1, 2, 10, 1_000_000] {
+                assert_eq!(
+                    solution(input, factor),
+                    brute_force(input, factor),
+                    "{input}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pair_distances() {
+        let universe = Universe::new(EXAMPLE, 2, 2);
+        assert_eq!(universe.len(), 9);
+        assert_eq!(universe.distance(4, 8), 9);
+        assert_eq!(universe.distance(0, 6), 15);
+        assert_eq!(universe.distance(2, 5), 17);
+        assert_eq!(universe.distance(7, 8), 5);
+        assert_eq!(universe.histogram().values().sum::<usize>(), 36);
+        assert_eq!(
+            universe
+                .histogram()
+                .iter()
+                .map(|(d, n)| d * n)
+                .sum::<usize>(),
+            374
+        );
+    }
+
+    #[test]
+    fn nearest() {
+        let universe = Universe::new(EXAMPLE, 2, 2);
+        assert_eq!(universe.nearest(7), Some((8, 5)));
+        assert_eq!(universe.nearest(0), Some((1, 6)));
+        assert_eq!(Universe::new("#..", 2, 2).nearest(0), None);
+    }
+
+    #[test]
+    fn separate_factors() {
+        let universe = Universe::new("#..\n...\n..#", 10, 1);
+        assert_eq!(universe.galaxy(1), (11, 2));
+        assert_eq!(universe.distance(0, 1), 13);
+        let universe = Universe::new("#..\n...\n..#", 1, 10);
+        assert_eq!(universe.galaxy(1), (2, 11));
+        assert_eq!(universe.distance(0, 1), 13);
+    }
+}
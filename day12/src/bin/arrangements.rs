@@ -0,0 +1,52 @@
+use day12::{render, Arrangements, Row};
+
+const USAGE: &str =
+    "usage: arrangements '<springs> <groups>' [--unfold n] [--list n] [--nth k] [--sample n]";
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(line) = args.next() else {
+        eprintln!("{USAGE}");
+        std::process::exit(1);
+    };
+
+    let mut factor = 1;
+    let mut list = 0;
+    let mut nth = vec![];
+    let mut samples = 0;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{USAGE}"));
+        match arg.as_str() {
+            "--unfold" => factor = value().parse().unwrap(),
+            "--list" => list = value().parse().unwrap(),
+            "--nth" => nth.push(value().parse::<u128>().unwrap()),
+            "--sample" => samples = value().parse().unwrap(),
+            _ => panic!("{USAGE}"),
+        }
+    }
+
+    let row = Row::parse(&line).unfold(factor);
+    let arrangements = Arrangements::new(&row);
+    println!("{} arrangements", arrangements.count());
+
+    for (k, springs) in arrangements.iter().take(list).enumerate() {
+        println!("{k:>8} {}", render(&springs));
+    }
+    for k in nth {
+        match arrangements.nth(k) {
+            Some(springs) => println!("{k:>8} {}", render(&springs)),
+            None => println!("{k:>8} out of range"),
+        }
+    }
+
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+    let mut next = day12::splitmix64(seed);
+    for _ in 0..samples {
+        if let Some(springs) = arrangements.sample(&mut next) {
+            println!("  random {}", render(&springs));
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Instant;
+
+/// Builds a random valid row, then hides some of its springs behind `?`.
+fn generate(lines: usize, seed: u64) -> String {
+    let mut bits = day12::splitmix64(seed);
+    let mut next = move |n: u64| bits() % n;
+
+    (0..lines)
+        .map(|_| {
+            let groups = (0..2 + next(4))
+                .map(|_| 1 + next(4) as usize)
+                .collect::<Vec<_>>();
+            let mut springs = ".".repeat(next(3) as usize);
+            for group in &groups {
+                springs += &"#".repeat(*group);
+                springs += &".".repeat(1 + next(3) as usize);
+            }
+            let springs = springs
+                .chars()
+                .map(|ch| if next(3) > 0 { ch } else { '?' })
+                .collect::<String>();
+            let nums = groups.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+            format!("{springs} {}", nums.join(","))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let lines = args.next().map_or(20000, |arg| arg.parse().unwrap());
+    let factor = args.next().map_or(10, |arg| arg.parse().unwrap());
+    let input = generate(lines, 2023);
+
+    let mut baseline = None;
+    for threads in [Some(1), None] {
+        let start = Instant::now();
+        let res = day12::with_threads(threads, || day12::solution(&input, factor));
+        let elapsed = start.elapsed();
+
+        let threads = threads.map_or("all".to_string(), |n| n.to_string());
+        print!("{lines} lines, unfold {factor}, {threads:>3} threads: {res} in {elapsed:.2?}");
+        match baseline {
+            None => {
+                baseline = Some(elapsed);
+                println!();
+            }
+            Some(baseline) => println!(" ({:.1}x)", baseline.as_secs_f64() / elapsed.as_secs_f64()),
+        }
+    }
+}
@@ -0,0 +1,527 @@
+use rayon::prelude::*;
+use std::fmt;
+
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl fmt::Debug for Spring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Damaged => '#',
+                Self::Operational => '.',
+                Self::Unknown => '?',
+            }
+        )
+    }
+}
+
+impl From<char> for Spring {
+    fn from(value: char) -> Self {
+        match value {
+            '#' => Spring::Damaged,
+            '.' => Spring::Operational,
+            '?' => Self::Unknown,
+            _ => panic!("{value}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Params {
+    seq: usize,
+    ptr: usize,
+    amt: u128,
+    prev: Option<Spring>,
+}
+
+impl Params {
+    fn from(seq: usize, ptr: usize, amt: u128, prev: Option<Spring>) -> Self {
+        Self {
+            seq,
+            ptr,
+            amt,
+            prev,
+        }
+    }
+}
+
+fn damaged(new_groups: &mut Vec<Params>, g: &Params, nums: &[usize]) {
+    if g.ptr < nums.len() && g.seq < nums[g.ptr] {
+        new_groups.push(Params::from(g.seq + 1, g.ptr, g.amt, Some(Spring::Damaged)));
+    }
+}
+
+fn operational(new_groups: &mut Vec<Params>, g: &Params, nums: &[usize]) {
+    if g.prev == Some(Spring::Damaged) {
+        if g.ptr < nums.len() && nums[g.ptr] == g.seq {
+            new_groups.push(Params::from(0, g.ptr + 1, g.amt, Some(Spring::Operational)));
+        }
+    } else {
+        new_groups.push(Params::from(0, g.ptr, g.amt, Some(Spring::Operational)));
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Row {
+    pub springs: Vec<Spring>,
+    pub nums: Vec<usize>,
+}
+
+impl Row {
+    pub fn parse(line: &str) -> Self {
+        let (springs, nums) = line.split_at(line.find(' ').unwrap());
+        let springs = springs.trim().chars().map(Spring::from).collect();
+        let nums = nums
+            .trim()
+            .split(',')
+            .map(|num| {
+                num.parse::<usize>().unwrap_or_else(|_| {
+                    panic!("'{num}'");
+                })
+            })
+            .collect();
+
+        Self { springs, nums }
+    }
+
+    /// Repeats the row `factor` times, with an unknown spring between the copies.
+    pub fn unfold(&self, factor: usize) -> Self {
+        let mut springs = vec![];
+        for i in 0..factor {
+            if i > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Self {
+            springs,
+            nums: self.nums.repeat(factor),
+        }
+    }
+
+    /// Counts arrangements by walking the row once while keeping every distinct
+    /// `(run length, group index, previous spring)` state with how many ways reach it.
+    ///
+    /// Panics if the count does not fit into a `u128`.
+    pub fn arrangements(&self) -> u128 {
+        let nums = &self.nums;
+        let mut groups = vec![Params::from(0, 0, 1, None)];
+
+        for s in self.springs.iter().chain([&Spring::Operational]) {
+            let mut new_groups = vec![];
+            for g in groups {
+                match s {
+                    Spring::Unknown => {
+                        damaged(&mut new_groups, &g, nums);
+                        operational(&mut new_groups, &g, nums);
+                    }
+                    Spring::Damaged => damaged(&mut new_groups, &g, nums),
+                    Spring::Operational => operational(&mut new_groups, &g, nums),
+                }
+            }
+
+            new_groups.sort();
+
+            groups = new_groups.into_iter().fold(Vec::new(), |mut vec, elem| {
+                if let Some(last) = vec.last_mut() {
+                    if last.seq == elem.seq && last.ptr == elem.ptr && last.prev == elem.prev {
+                        last.amt = last
+                            .amt
+                            .checked_add(elem.amt)
+                            .expect("arrangement count overflows u128");
+                        return vec;
+                    }
+                }
+                vec.push(elem);
+                vec
+            });
+        }
+
+        groups
+            .into_iter()
+            .filter(|g| g.ptr == nums.len())
+            .map(|g| g.amt)
+            .sum()
+    }
+}
+
+/// Completion counts for one row, laid out so every arrangement has an index
+/// in lexicographic order (`#` sorts before `.`).
+#[derive(Clone, Debug)]
+pub struct Arrangements {
+    row: Row,
+    /// Number of non-operational springs starting at each position.
+    runs: Vec<usize>,
+    /// `ways[i][j]`: ways to fill `springs[i..]` with groups `nums[j..]`,
+    /// when position `i` does not continue a group.
+    ways: Vec<Vec<u128>>,
+}
+
+impl Arrangements {
+    pub fn new(row: &Row) -> Self {
+        let len = row.springs.len();
+        let groups = row.nums.len();
+
+        let mut runs = vec![0; len + 1];
+        for i in (0..len).rev() {
+            if row.springs[i] != Spring::Operational {
+                runs[i] = runs[i + 1] + 1;
+            }
+        }
+
+        let mut res = Self {
+            row: row.clone(),
+            runs,
+            ways: vec![vec![0; groups + 1]; len + 1],
+        };
+        res.ways[len][groups] = 1;
+        for i in (0..len).rev() {
+            for j in 0..=groups {
+                let mut ways = 0u128;
+                if row.springs[i] != Spring::Damaged {
+                    ways = res.ways[i + 1][j];
+                }
+                if let Some(next) = res.group_end(i, j) {
+                    ways = ways
+                        .checked_add(res.ways[next][j + 1])
+                        .expect("arrangement count overflows u128");
+                }
+                res.ways[i][j] = ways;
+            }
+        }
+
+        res
+    }
+
+    /// Where the next group starts looking after placing group `j` at `i`,
+    /// or `None` if it does not fit there.
+    fn group_end(&self, i: usize, j: usize) -> Option<usize> {
+        let len = self.row.springs.len();
+        let num = *self.row.nums.get(j)?;
+        let fits =
+            self.runs[i] >= num && (i + num == len || self.row.springs[i + num] != Spring::Damaged);
+        fits.then_some((i + num + 1).min(len))
+    }
+
+    pub fn count(&self) -> u128 {
+        self.ways[0][0]
+    }
+
+    /// Springs that are the same in every arrangement keep that state, the rest
+    /// become `Unknown`. `None` if the row has no arrangement at all.
+    pub fn forced(&self) -> Option<Vec<Spring>> {
+        if self.count() == 0 {
+            return None;
+        }
+
+        let len = self.row.springs.len();
+        let groups = self.row.nums.len();
+        let mut can_damage = vec![false; len];
+        let mut can_operate = vec![false; len];
+        let mut reached = vec![vec![false; groups + 1]; len + 1];
+        reached[0][0] = true;
+
+        for i in 0..len {
+            for j in 0..=groups {
+                if !reached[i][j] {
+                    continue;
+                }
+                if self.row.springs[i] != Spring::Damaged && self.ways[i + 1][j] > 0 {
+                    can_operate[i] = true;
+                    reached[i + 1][j] = true;
+                }
+                if let Some(next) = self.group_end(i, j) {
+                    if self.ways[next][j + 1] > 0 {
+                        let end = i + self.row.nums[j];
+                        can_damage[i..end].iter_mut().for_each(|cell| *cell = true);
+                        can_operate[end..next]
+                            .iter_mut()
+                            .for_each(|cell| *cell = true);
+                        reached[next][j + 1] = true;
+                    }
+                }
+            }
+        }
+
+        let res = can_damage
+            .into_iter()
+            .zip(can_operate)
+            .map(|states| match states {
+                (true, false) => Spring::Damaged,
+                (false, true) => Spring::Operational,
+                _ => Spring::Unknown,
+            })
+            .collect();
+        Some(res)
+    }
+
+    /// The `k`-th arrangement in lexicographic order, counting from 0.
+    pub fn nth(&self, mut k: u128) -> Option<Vec<Spring>> {
+        if k >= self.count() {
+            return None;
+        }
+
+        let len = self.row.springs.len();
+        let mut res = Vec::with_capacity(len);
+        let (mut i, mut j) = (0, 0);
+        while i < len {
+            if let Some(next) = self.group_end(i, j) {
+                let ways = self.ways[next][j + 1];
+                if k < ways {
+                    res.extend(std::iter::repeat_n(Spring::Damaged, self.row.nums[j]));
+                    res.extend(std::iter::repeat_n(
+                        Spring::Operational,
+                        next - i - self.row.nums[j],
+                    ));
+                    (i, j) = (next, j + 1);
+                    continue;
+                }
+                k -= ways;
+            }
+            res.push(Spring::Operational);
+            i += 1;
+        }
+
+        Some(res)
+    }
+
+    /// Every arrangement in lexicographic order, built one at a time.
+    pub fn iter(&self) -> impl Iterator<Item = Vec<Spring>> + '_ {
+        (0..self.count()).map(|k| self.nth(k).unwrap())
+    }
+
+    /// A uniformly random arrangement, drawing random bits from `next_u64`.
+    pub fn sample(&self, mut next_u64: impl FnMut() -> u64) -> Option<Vec<Spring>> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+
+        // Throw away draws from the incomplete last block, so every index is equally likely.
+        let limit = u128::MAX - u128::MAX % count;
+        loop {
+            let draw = (next_u64() as u128) << 64 | next_u64() as u128;
+            if draw < limit {
+                return self.nth(draw % count);
+            }
+        }
+    }
+}
+
+/// The splitmix64 generator: a source of random bits for
+/// [`Arrangements::sample`], and for generating test rows.
+pub fn splitmix64(mut seed: u64) -> impl FnMut() -> u64 {
+    move || {
+        seed = seed.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+pub fn render(springs: &[Spring]) -> String {
+    springs.iter().map(|s| format!("{s:?}")).collect()
+}
+
+pub fn arrangements(line: &str, factor: usize) -> u128 {
+    Row::parse(line).unfold(factor).arrangements()
+}
+
+/// Arrangement count of every line, in input order. Lines are counted in
+/// parallel on the current rayon pool.
+pub fn counts(input: &str, factor: usize) -> Vec<u128> {
+    input
+        .par_lines()
+        .map(|line| arrangements(line, factor))
+        .collect()
+}
+
+/// Sum of the per-line counts, checked like the counts themselves.
+pub fn total(counts: &[u128]) -> u128 {
+    counts
+        .iter()
+        .try_fold(0u128, |sum, &count| sum.checked_add(count))
+        .expect("arrangement count overflows u128")
+}
+
+pub fn solution(input: &str, factor: usize) -> u128 {
+    total(&counts(input, factor))
+}
+
+/// Runs `f` on a pool with the given number of threads, or on rayon's global
+/// pool (one thread per core unless `RAYON_NUM_THREADS` says otherwise) for `None`.
+pub fn with_threads<T: Send>(threads: Option<usize>, f: impl FnOnce() -> T + Send) -> T {
+    match threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+            .install(f),
+        None => f(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        arrangements, counts, render, splitmix64, total, with_threads, Arrangements, Row, Spring,
+    };
+
+    #[test]
+    fn per_line_counts() {
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+        assert_eq!(counts(input, 1), [1, 4, 1, 1, 4, 10]);
+        assert_eq!(counts(input, 5), [1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn big_unfold_factors() {
+        let line = ".??..??...?##. 1,1,3";
+        assert_eq!(arrangements(line, 10), 4 * 8u128.pow(9));
+        assert_eq!(arrangements(line, 20), 4 * 8u128.pow(19));
+        assert_eq!(arrangements(line, 40), 4 * 8u128.pow(39));
+    }
+
+    #[test]
+    #[should_panic(expected = "arrangement count overflows u128")]
+    fn total_overflows() {
+        assert_eq!(total(&[1, 4, u128::MAX - 5]), u128::MAX);
+        total(&[u128::MAX, 1]);
+    }
+
+    #[test]
+    fn same_result_on_any_pool() {
+        let input = "?###???????? 3,2,1\n".repeat(50) + ".??..??...?##. 1,1,3";
+        let expected = counts(&input, 5);
+        for threads in [1, 2, 7] {
+            assert_eq!(with_threads(Some(threads), || counts(&input, 5)), expected);
+        }
+        assert_eq!(expected.last(), Some(&16384));
+    }
+
+    fn groups(springs: &[Spring]) -> Vec<usize> {
+        springs
+            .split(|s| *s == Spring::Operational)
+            .map(|run| run.len())
+            .filter(|len| *len > 0)
+            .collect()
+    }
+
+    fn valid(row: &Row, springs: &[Spring]) -> bool {
+        springs.len() == row.springs.len()
+            && row
+                .springs
+                .iter()
+                .zip(springs)
+                .all(|(pattern, s)| *pattern == Spring::Unknown || pattern == s)
+            && groups(springs) == row.nums
+    }
+
+    #[test]
+    fn enumerates_in_order() {
+        let row = Row::parse("?###???????? 3,2,1");
+        let arrangements = Arrangements::new(&row);
+        assert_eq!(arrangements.count(), 10);
+
+        let all = arrangements.iter().map(|s| render(&s)).collect::<Vec<_>>();
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0], ".###.##.#...");
+        assert_eq!(all[9], ".###....##.#");
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(arrangements.iter().all(|s| valid(&row, &s)));
+        assert_eq!(arrangements.nth(10), None);
+    }
+
+    #[test]
+    fn counts_match_dp() {
+        for line in [
+            "???.### 1,1,3",
+            ".??..??...?##. 1,1,3",
+            "?#?#?#?#?#?#?#? 1,3,1,6",
+            "????.#...#... 4,1,1",
+            "????.######..#####. 1,6,5",
+            "?###???????? 3,2,1",
+        ] {
+            let row = Row::parse(line);
+            for factor in [1, 2, 5] {
+                let row = row.unfold(factor);
+                assert_eq!(
+                    Arrangements::new(&row).count(),
+                    row.arrangements(),
+                    "{line}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn indexes_unfolded_rows() {
+        let row = Row::parse("?###???????? 3,2,1").unfold(5);
+        let arrangements = Arrangements::new(&row);
+        assert_eq!(arrangements.count(), 506250);
+
+        let first = arrangements.nth(0).unwrap();
+        let second = arrangements.nth(1).unwrap();
+        let last = arrangements.nth(506249).unwrap();
+        assert!(render(&first) < render(&second) && render(&second) < render(&last));
+        assert!([first, second, last].iter().all(|s| valid(&row, s)));
+    }
+
+    #[test]
+    fn samples_valid_arrangements() {
+        let row = Row::parse("?###???????? 3,2,1");
+        let arrangements = Arrangements::new(&row);
+        let mut next = splitmix64(7);
+
+        let mut seen = vec![0; 10];
+        for _ in 0..1000 {
+            let s = arrangements.sample(&mut next).unwrap();
+            assert!(valid(&row, &s));
+            let k = arrangements.iter().position(|other| other == s).unwrap();
+            seen[k] += 1;
+        }
+        assert!(seen.iter().all(|n| *n > 50), "{seen:?}");
+        assert_eq!(Arrangements::new(&Row::parse("# 2")).sample(next), None);
+    }
+
+    #[test]
+    fn forced_springs() {
+        let forced = |line| {
+            Arrangements::new(&Row::parse(line))
+                .forced()
+                .map(|s| render(&s))
+        };
+        assert_eq!(
+            forced("?###???????? 3,2,1").as_deref(),
+            Some(".###.???????")
+        );
+        assert_eq!(forced("??????? 4,2").as_deref(), Some("####.##"));
+        assert_eq!(forced("????? 3").as_deref(), Some("??#??"));
+        let empty = Row {
+            springs: "??.??".chars().map(Spring::from).collect(),
+            nums: vec![],
+        };
+        assert_eq!(
+            Arrangements::new(&empty)
+                .forced()
+                .map(|s| render(&s))
+                .as_deref(),
+            Some(".....")
+        );
+        assert_eq!(forced("#?# 1").as_deref(), None);
+    }
+}
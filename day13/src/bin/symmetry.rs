@@ -0,0 +1,11 @@
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else {
+        eprintln!("usage: symmetry <input> [smudges]");
+        std::process::exit(1);
+    };
+    let smudges = args.next().map_or(0, |arg| arg.parse().unwrap());
+
+    let input = std::fs::read_to_string(path).unwrap();
+    print!("{}", day13::symmetry_report(&input, smudges));
+}
@@ -0,0 +1,566 @@
+use std::fmt;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Tile {
+    Ash,
+    Rock,
+}
+
+impl fmt::Debug for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Tile::Ash => '.',
+                Tile::Rock => '#',
+            }
+        )
+    }
+}
+
+impl From<char> for Tile {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => Tile::Ash,
+            '#' => Tile::Rock,
+            _ => panic!("{value}"),
+        }
+    }
+}
+
+/// Mirror line of a pattern, given by how many rows are above it or how many
+/// columns are left of it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+    Horizontal(usize),
+    Vertical(usize),
+}
+
+impl Axis {
+    pub fn score(&self) -> usize {
+        match self {
+            Axis::Horizontal(rows) => rows * 100,
+            Axis::Vertical(cols) => *cols,
+        }
+    }
+}
+
+/// A pattern with every row and column packed into a bitmask, rocks as ones,
+/// so the number of differing tiles between two lines is a popcount. Lines
+/// longer than 64 tiles take several words.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Pattern {
+    pub tiles: Vec<Vec<Tile>>,
+    rows: Vec<Vec<u64>>,
+    cols: Vec<Vec<u64>>,
+}
+
+impl Pattern {
+    pub fn parse(field: &str) -> Self {
+        let tiles = field
+            .lines()
+            .map(|line| line.trim().chars().map(Tile::from).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        Self::new(tiles)
+    }
+
+    pub fn new(tiles: Vec<Vec<Tile>>) -> Self {
+        let height = tiles.len();
+        let width = tiles.first().map_or(0, |row| row.len());
+
+        let mut rows = vec![vec![0u64; width.div_ceil(64)]; height];
+        let mut cols = vec![vec![0u64; height.div_ceil(64)]; width];
+        for (i, row) in tiles.iter().enumerate() {
+            for (j, tile) in row.iter().enumerate() {
+                if *tile == Tile::Rock {
+                    rows[i][j / 64] |= 1 << (j % 64);
+                    cols[j][i / 64] |= 1 << (i % 64);
+                }
+            }
+        }
+
+        Self { tiles, rows, cols }
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Every mirror line for which exactly `smudges` tiles differ from their reflection.
+    pub fn reflections(&self, smudges: usize) -> Vec<Axis> {
+        let horizontal = mirrors(&self.rows, smudges).map(Axis::Horizontal);
+        let vertical = mirrors(&self.cols, smudges).map(Axis::Vertical);
+        horizontal.chain(vertical).collect()
+    }
+}
+
+/// The smudge of a pattern and the mirror lines before and after cleaning it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fix {
+    /// `(row, col)` of the smudge. Either of the two mismatched tiles would do;
+    /// the one that comes first in reading order is reported.
+    pub smudge: (usize, usize),
+    pub old: Option<Axis>,
+    pub new: Axis,
+}
+
+impl Pattern {
+    /// Finds the one tile whose flip makes a new mirror line appear.
+    pub fn fix(&self) -> Option<Fix> {
+        let new = *self.reflections(1).first()?;
+        let (lines, axis) = match new {
+            Axis::Horizontal(axis) => (&self.rows, axis),
+            Axis::Vertical(axis) => (&self.cols, axis),
+        };
+
+        let (line, other) = (0..axis)
+            .rev()
+            .zip(axis..lines.len())
+            .find(|&(a, b)| lines[a] != lines[b])
+            .unwrap();
+        let bit = lines[line]
+            .iter()
+            .zip(&lines[other])
+            .enumerate()
+            .find(|(_, (a, b))| a != b)
+            .map(|(word, (a, b))| word * 64 + (a ^ b).trailing_zeros() as usize)
+            .unwrap();
+        let smudge = match new {
+            Axis::Horizontal(_) => (line, bit),
+            Axis::Vertical(_) => (bit, line),
+        };
+
+        Some(Fix {
+            smudge,
+            old: self.reflections(0).first().copied(),
+            new,
+        })
+    }
+
+    /// The pattern with the tile at `(row, col)` turned from ash to rock or back.
+    pub fn flip(&self, (row, col): (usize, usize)) -> Self {
+        let mut tiles = self.tiles.clone();
+        tiles[row][col] = match tiles[row][col] {
+            Tile::Ash => Tile::Rock,
+            Tile::Rock => Tile::Ash,
+        };
+        Self::new(tiles)
+    }
+
+    /// Draws the pattern like the puzzle does: `><` above and below a vertical
+    /// mirror line, `v` and `^` on both sides of a horizontal one. The marked
+    /// tile is drawn as `X`.
+    pub fn render(&self, axis: Option<Axis>, mark: Option<(usize, usize)>) -> String {
+        let vertical = |res: &mut String| {
+            if let Some(Axis::Vertical(cols)) = axis {
+                *res += &format!(" {}><\n", " ".repeat(cols - 1));
+            }
+        };
+
+        let mut res = String::new();
+        vertical(&mut res);
+        for (i, row) in self.tiles.iter().enumerate() {
+            let side = match axis {
+                Some(Axis::Horizontal(rows)) if i + 1 == rows => 'v',
+                Some(Axis::Horizontal(rows)) if i == rows => '^',
+                _ => ' ',
+            };
+            res.push(side);
+            for (j, tile) in row.iter().enumerate() {
+                match mark == Some((i, j)) {
+                    true => res.push('X'),
+                    false => res += &format!("{tile:?}"),
+                }
+            }
+            res.push(side);
+            res.push('\n');
+        }
+        vertical(&mut res);
+        res
+    }
+}
+
+/// For every pattern: where its smudge is, how the mirror line moves, and the
+/// cleaned pattern with the new line and the fixed tile drawn in.
+pub fn explain(input: &str) -> String {
+    let mut res = String::new();
+    for (i, pattern) in patterns(input).enumerate() {
+        match pattern.fix() {
+            Some(fix) => {
+                let (row, col) = fix.smudge;
+                res += &format!(
+                    "pattern {}: smudge at row {row}, col {col}; {:?} -> {:?}\n",
+                    i + 1,
+                    fix.old,
+                    fix.new
+                );
+                res += &pattern
+                    .flip(fix.smudge)
+                    .render(Some(fix.new), Some(fix.smudge));
+            }
+            None => res += &format!("pattern {}: no smudge found\n", i + 1),
+        }
+        res.push('\n');
+    }
+    res
+}
+
+/// A symmetry of a pattern. Like the mirror lines, they are partial: tiles
+/// whose image falls outside the pattern are not compared.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Symmetry {
+    Axis(Axis),
+    /// Mirror line `col - row = offset`, running down to the right.
+    Diagonal(isize),
+    /// Mirror line `row + col = sum`, running down to the left.
+    AntiDiagonal(usize),
+    /// Half turn about `(row / 2, col / 2)`; the centre is stored doubled so
+    /// centres between tiles stay whole numbers.
+    Rotation(usize, usize),
+}
+
+impl Pattern {
+    fn tile(&self, (row, col): (usize, usize)) -> Tile {
+        self.tiles[row][col]
+    }
+
+    fn inside(&self, row: isize, col: isize) -> Option<(usize, usize)> {
+        let inside =
+            (0..self.height() as isize).contains(&row) && (0..self.width() as isize).contains(&col);
+        inside.then_some((row as usize, col as usize))
+    }
+
+    /// Counts tile pairs that `image` maps onto each other but which differ,
+    /// giving up once there are more than `limit`. `None` if nothing maps onto
+    /// another tile of the pattern at all.
+    fn mismatched_pairs(
+        &self,
+        image: impl Fn(isize, isize) -> (isize, isize),
+        limit: usize,
+    ) -> Option<usize> {
+        let mut pairs = 0;
+        let mut ctr = 0;
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                let (r, c) = image(row as isize, col as isize);
+                let Some(other) = self.inside(r, c) else {
+                    continue;
+                };
+                if other <= (row, col) {
+                    continue;
+                }
+                pairs += 1;
+                if self.tile((row, col)) != self.tile(other) {
+                    ctr += 1;
+                    if ctr > limit {
+                        return Some(ctr);
+                    }
+                }
+            }
+        }
+        (pairs > 0).then_some(ctr)
+    }
+
+    /// Every symmetry that holds with exactly `smudges` mismatched tile pairs.
+    pub fn symmetries(&self, smudges: usize) -> Vec<Symmetry> {
+        let (height, width) = (self.height() as isize, self.width() as isize);
+        let holds = |pairs: Option<usize>| pairs == Some(smudges);
+
+        let axes = self.reflections(smudges).into_iter().map(Symmetry::Axis);
+        let diagonals = (1 - height..width)
+            .filter(|&d| holds(self.mismatched_pairs(|r, c| (c - d, r + d), smudges)))
+            .map(Symmetry::Diagonal);
+        let anti_diagonals = (0..height + width - 1)
+            .filter(|&s| holds(self.mismatched_pairs(|r, c| (s - c, s - r), smudges)))
+            .map(|s| Symmetry::AntiDiagonal(s as usize));
+        let rotations = (0..2 * height - 1)
+            .flat_map(|r2| (0..2 * width - 1).map(move |c2| (r2, c2)))
+            .filter(|&(r2, c2)| holds(self.mismatched_pairs(|r, c| (r2 - r, c2 - c), smudges)))
+            .map(|(r2, c2)| Symmetry::Rotation(r2 as usize, c2 as usize));
+
+        axes.chain(diagonals)
+            .chain(anti_diagonals)
+            .chain(rotations)
+            .collect()
+    }
+}
+
+/// Lists the symmetries of every pattern, one pattern per line.
+pub fn symmetry_report(input: &str, smudges: usize) -> String {
+    patterns(input)
+        .enumerate()
+        .map(|(i, pattern)| {
+            let symmetries = pattern
+                .symmetries(smudges)
+                .iter()
+                .map(|symmetry| format!("{symmetry:?}"))
+                .collect::<Vec<_>>();
+            format!("pattern {}: {}\n", i + 1, symmetries.join(", "))
+        })
+        .collect()
+}
+
+/// Counts differing tiles between the lines mirrored across the axis before
+/// `axis`, giving up once there are more than `limit`.
+fn mismatches(lines: &[Vec<u64>], axis: usize, limit: usize) -> usize {
+    let mut ctr = 0;
+    for (a, b) in lines[..axis].iter().rev().zip(&lines[axis..]) {
+        ctr += a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum::<usize>();
+        if ctr > limit {
+            break;
+        }
+    }
+    ctr
+}
+
+fn mirrors(lines: &[Vec<u64>], smudges: usize) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |&axis| mismatches(lines, axis, smudges) == smudges)
+}
+
+pub fn patterns(input: &str) -> impl Iterator<Item = Pattern> + '_ {
+    input
+        .split("\n\n")
+        .filter(|field| !field.trim().is_empty())
+        .map(Pattern::parse)
+}
+
+pub fn solution(input: &str, smudges: usize) -> usize {
+    patterns(input)
+        .flat_map(|pattern| pattern.reflections(smudges))
+        .map(|axis| axis.score())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{explain, symmetry_report, Axis, Fix, Pattern, Symmetry};
+
+    const EXAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn all_axes() {
+        let pattern = Pattern::parse(
+            "#..#
+....
+....
+#..#",
+        );
+        assert_eq!(
+            pattern.reflections(0),
+            [Axis::Horizontal(2), Axis::Vertical(2)]
+        );
+
+        let pattern = Pattern::parse(
+            "##..##
+##..##",
+        );
+        assert_eq!(
+            pattern.reflections(0),
+            [
+                Axis::Horizontal(1),
+                Axis::Vertical(1),
+                Axis::Vertical(3),
+                Axis::Vertical(5)
+            ]
+        );
+    }
+
+    #[test]
+    fn smudge_budget() {
+        let pattern = Pattern::parse(
+            "#.##
+.#..
+....
+#...",
+        );
+        assert_eq!(pattern.reflections(0), [Axis::Vertical(3)]);
+        assert_eq!(pattern.reflections(1), [Axis::Horizontal(3)]);
+        assert_eq!(pattern.reflections(2), []);
+        assert_eq!(
+            pattern.reflections(3),
+            [Axis::Horizontal(2), Axis::Vertical(1), Axis::Vertical(2)]
+        );
+    }
+
+    #[test]
+    fn smudges_from_the_example() {
+        let fixes = super::patterns(EXAMPLE)
+            .map(|pattern| pattern.fix())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fixes,
+            [
+                Some(Fix {
+                    smudge: (0, 0),
+                    old: Some(Axis::Vertical(5)),
+                    new: Axis::Horizontal(3),
+                }),
+                Some(Fix {
+                    smudge: (0, 4),
+                    old: Some(Axis::Horizontal(4)),
+                    new: Axis::Horizontal(1),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn fixed_pattern_reflects() {
+        for pattern in super::patterns(EXAMPLE) {
+            let fix = pattern.fix().unwrap();
+            assert!(pattern.flip(fix.smudge).reflections(0).contains(&fix.new));
+        }
+    }
+
+    #[test]
+    fn renders_fix() {
+        let res = explain(EXAMPLE);
+        assert_eq!(
+            res,
+            "pattern 1: smudge at row 0, col 0; Some(Vertical(5)) -> Horizontal(3)
+ X.##..##. 
+ ..#.##.#. 
+v##......#v
+^##......#^
+ ..#.##.#. 
+ ..##..##. 
+ #.#.##.#. 
+
+pattern 2: smudge at row 0, col 4; Some(Horizontal(4)) -> Horizontal(1)
+v#...X#..#v
+^#....#..#^
+ ..##..### 
+ #####.##. 
+ #####.##. 
+ ..##..### 
+ #....#..# 
+
+"
+        );
+        let pattern = Pattern::parse("#..#\n.##.");
+        assert_eq!(
+            pattern.render(Some(Axis::Vertical(2)), None),
+            "  ><\n #..# \n .##. \n  ><\n"
+        );
+    }
+
+    #[test]
+    fn diagonal_and_rotational_symmetries() {
+        let pattern = Pattern::parse("#.\n.#");
+        assert_eq!(
+            pattern.symmetries(0),
+            [
+                Symmetry::Diagonal(0),
+                Symmetry::AntiDiagonal(1),
+                Symmetry::Rotation(1, 1)
+            ]
+        );
+        assert_eq!(
+            pattern.symmetries(1),
+            [
+                Symmetry::Rotation(0, 1),
+                Symmetry::Rotation(1, 0),
+                Symmetry::Rotation(1, 2),
+                Symmetry::Rotation(2, 1)
+            ]
+        );
+        assert_eq!(
+            pattern.symmetries(2),
+            [
+                Symmetry::Axis(Axis::Horizontal(1)),
+                Symmetry::Axis(Axis::Vertical(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn partial_symmetries() {
+        // A half turn about the centre maps the whole pattern onto itself,
+        // the other symmetries only hold where the image overlaps the pattern.
+        let pattern = Pattern::parse(
+            "..##
+#..#
+##..",
+        );
+        assert_eq!(
+            pattern.symmetries(0),
+            [
+                Symmetry::AntiDiagonal(1),
+                Symmetry::AntiDiagonal(2),
+                Symmetry::AntiDiagonal(3),
+                Symmetry::AntiDiagonal(4),
+                Symmetry::Rotation(0, 1),
+                Symmetry::Rotation(0, 5),
+                Symmetry::Rotation(1, 2),
+                Symmetry::Rotation(1, 6),
+                Symmetry::Rotation(2, 3),
+                Symmetry::Rotation(3, 0),
+                Symmetry::Rotation(3, 4),
+                Symmetry::Rotation(4, 1),
+                Symmetry::Rotation(4, 5)
+            ]
+        );
+
+        let smudged = pattern.flip((0, 2));
+        assert!(!smudged.symmetries(0).contains(&Symmetry::AntiDiagonal(3)));
+        assert!(smudged.symmetries(1).contains(&Symmetry::AntiDiagonal(3)));
+    }
+
+    #[test]
+    fn report() {
+        let res = symmetry_report("#.\n.#\n\n##\n..", 0);
+        assert_eq!(
+            res,
+            "pattern 1: Diagonal(0), AntiDiagonal(1), Rotation(1, 1)
+pattern 2: Axis(Vertical(1)), Rotation(0, 1), Rotation(2, 1)
+"
+        );
+    }
+
+    #[test]
+    fn wider_than_a_word() {
+        let pattern = Pattern::parse(&format!("{}#\n{}", ".".repeat(64), ".".repeat(65)));
+        assert_eq!(pattern.width(), 65);
+        assert_eq!(
+            pattern.reflections(0),
+            (1..=32).map(Axis::Vertical).collect::<Vec<_>>()
+        );
+        let fix = pattern.fix().unwrap();
+        assert_eq!((fix.smudge, fix.new), ((0, 64), Axis::Horizontal(1)));
+
+        let pattern = Pattern::parse(&format!("{}#.", "..\n".repeat(64)));
+        assert_eq!(pattern.height(), 65);
+        assert_eq!(
+            pattern.reflections(0),
+            (1..=32).map(Axis::Horizontal).collect::<Vec<_>>()
+        );
+        assert!(pattern.reflections(1).contains(&Axis::Vertical(1)));
+        assert!(pattern
+            .flip((64, 0))
+            .reflections(0)
+            .contains(&Axis::Vertical(1)));
+    }
+}
@@ -0,0 +1,30 @@
+use day14::{Dir, Platform, Program};
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let print = args.iter().any(|arg| arg == "--print");
+    let args = args
+        .iter()
+        .filter(|arg| *arg != "--print")
+        .collect::<Vec<_>>();
+    let [path, program] = args[..] else {
+        eprintln!("usage: program <input> <program> [--print]");
+        eprintln!("e.g.   program input1.txt '(N W S E)*1000000000' --print");
+        std::process::exit(1);
+    };
+
+    let program = Program::parse(program).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+    let input = std::fs::read_to_string(path).unwrap();
+    let mut platform = Platform::parse(&input);
+
+    program.run(&mut platform, |item, platform| {
+        let loads = Dir::CYCLE.map(|edge| format!("{} {}", edge.letter(), platform.load_on(edge)));
+        println!("{item}: {}", loads.join(", "));
+    });
+    if print {
+        print!("{platform}");
+    }
+}
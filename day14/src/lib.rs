@@ -0,0 +1,524 @@
+use std::{fmt, iter::Peekable, str::CharIndices};
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Tile {
+    Empty,
+    Cube,
+    Rounded,
+}
+
+impl fmt::Debug for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Tile::Empty => '.',
+                Tile::Cube => '#',
+                Tile::Rounded => 'O',
+            }
+        )
+    }
+}
+
+impl From<char> for Tile {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => Tile::Empty,
+            '#' => Tile::Cube,
+            'O' => Tile::Rounded,
+            _ => panic!("{value}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Dir {
+    North,
+    West,
+    South,
+    East,
+}
+
+impl Dir {
+    /// The order of one spin cycle.
+    pub const CYCLE: [Dir; 4] = [Dir::North, Dir::West, Dir::South, Dir::East];
+
+    pub fn letter(self) -> char {
+        match self {
+            Dir::North => 'N',
+            Dir::West => 'W',
+            Dir::South => 'S',
+            Dir::East => 'E',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        Self::CYCLE.into_iter().find(|dir| dir.letter() == letter)
+    }
+}
+
+/// Bits `from..to` set.
+fn mask(from: usize, to: usize) -> u128 {
+    let low = |n: usize| if n >= 128 { !0 } else { (1 << n) - 1 };
+    low(to) & !low(from)
+}
+
+/// Set bit positions, lowest first.
+fn bits(mut row: u128) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        (row != 0).then(|| {
+            let bit = row.trailing_zeros() as usize;
+            row &= row - 1;
+            bit
+        })
+    })
+}
+
+/// One bitboard per row for each kind of rock, bit `j` being column `j`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Platform {
+    width: usize,
+    rounded: Vec<u128>,
+    cubes: Vec<u128>,
+}
+
+impl Platform {
+    pub fn parse(input: &str) -> Self {
+        let mut width = 0;
+        let mut rounded = vec![];
+        let mut cubes = vec![];
+
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            width = line.len();
+            assert!(width <= 128, "rows are limited to 128 tiles");
+
+            let (mut row_rounded, mut row_cubes) = (0, 0);
+            for (j, ch) in line.chars().enumerate() {
+                match Tile::from(ch) {
+                    Tile::Rounded => row_rounded |= 1 << j,
+                    Tile::Cube => row_cubes |= 1 << j,
+                    Tile::Empty => {}
+                }
+            }
+            rounded.push(row_rounded);
+            cubes.push(row_cubes);
+        }
+
+        Self {
+            width,
+            rounded,
+            cubes,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.rounded.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn tile(&self, (i, j): (usize, usize)) -> Tile {
+        if self.rounded[i] >> j & 1 == 1 {
+            Tile::Rounded
+        } else if self.cubes[i] >> j & 1 == 1 {
+            Tile::Cube
+        } else {
+            Tile::Empty
+        }
+    }
+
+    pub fn tilt(&mut self, dir: Dir) {
+        match dir {
+            Dir::North => self.tilt_vertical((0..self.height()).collect()),
+            Dir::South => self.tilt_vertical((0..self.height()).rev().collect()),
+            Dir::West => self.tilt_horizontal(false),
+            Dir::East => self.tilt_horizontal(true),
+        }
+    }
+
+    /// Walks the rows in `order`, keeping for every column the position in
+    /// `order` where the next rounded rock lands. Each rock is moved once.
+    fn tilt_vertical(&mut self, order: Vec<usize>) {
+        let mut next = vec![0; self.width];
+        let mut rounded = vec![0; self.height()];
+
+        for (k, &i) in order.iter().enumerate() {
+            for j in bits(self.rounded[i]) {
+                rounded[order[next[j]]] |= 1 << j;
+                next[j] += 1;
+            }
+            for j in bits(self.cubes[i]) {
+                next[j] = k + 1;
+            }
+        }
+
+        self.rounded = rounded;
+    }
+
+    /// Counts the rounded rocks between each pair of cubes and packs them
+    /// against one end of the gap.
+    fn tilt_horizontal(&mut self, east: bool) {
+        for (rounded, &cubes) in self.rounded.iter_mut().zip(&self.cubes) {
+            let mut res = 0;
+            let mut from = 0;
+            for to in bits(cubes).chain([self.width]) {
+                let count = (*rounded & mask(from, to)).count_ones() as usize;
+                res |= if east {
+                    mask(to - count, to)
+                } else {
+                    mask(from, from + count)
+                };
+                from = to + 1;
+            }
+            *rounded = res;
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        for dir in Dir::CYCLE {
+            self.tilt(dir);
+        }
+    }
+
+    /// Load on the north support beams.
+    pub fn load(&self) -> usize {
+        self.load_on(Dir::North)
+    }
+
+    /// Load against one edge: each rounded rock weighs its distance from the
+    /// opposite edge, counting its own tile.
+    pub fn load_on(&self, edge: Dir) -> usize {
+        let (h, w) = (self.height(), self.width);
+        self.rounded
+            .iter()
+            .enumerate()
+            .map(|(i, &row)| match edge {
+                Dir::North => row.count_ones() as usize * (h - i),
+                Dir::South => row.count_ones() as usize * (i + 1),
+                Dir::West => bits(row).map(|j| w - j).sum(),
+                Dir::East => bits(row).map(|j| j + 1).sum(),
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.height() {
+            for j in 0..self.width {
+                write!(f, "{:?}", self.tile((i, j)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// One step of a tilt program: a single tilt, or a group repeated `count` times.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Item {
+    Tilt(Dir),
+    Repeat(Vec<Item>, usize),
+}
+
+impl Item {
+    fn apply(&self, platform: &mut Platform) {
+        match self {
+            Item::Tilt(dir) => platform.tilt(*dir),
+            Item::Repeat(items, count) => {
+                *platform = cycle::nth(
+                    platform,
+                    |platform| items.iter().for_each(|item| item.apply(platform)),
+                    *count,
+                );
+            }
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::Tilt(dir) => write!(f, "{}", dir.letter()),
+            Item::Repeat(items, count) => {
+                write!(f, "({})", Program::join(items))?;
+                if *count != 1 {
+                    write!(f, "*{count}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Tilts such as `N E N`, with groups repeated like `(N W S E)*1000000000`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Program {
+    items: Vec<Item>,
+}
+
+impl Program {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut chars = input.char_indices().peekable();
+        let items = Self::parse_items(&mut chars, false)?;
+        Ok(Self { items })
+    }
+
+    fn parse_items(chars: &mut Peekable<CharIndices>, nested: bool) -> Result<Vec<Item>, String> {
+        let mut items = vec![];
+
+        loop {
+            while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+            let Some((pos, ch)) = chars.next() else {
+                if nested {
+                    return Err("unclosed `(`".to_string());
+                }
+                return Ok(items);
+            };
+
+            let item = match ch {
+                '(' => Item::Repeat(Self::parse_items(chars, true)?, 1),
+                ')' if nested => return Ok(items),
+                _ => match Dir::from_letter(ch) {
+                    Some(dir) => Item::Tilt(dir),
+                    None => return Err(format!("unexpected `{ch}` at {pos}")),
+                },
+            };
+
+            while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+            let item = match chars.next_if(|(_, ch)| *ch == '*') {
+                Some((pos, _)) => {
+                    while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+                    let mut digits = String::new();
+                    while let Some((_, digit)) = chars.next_if(|(_, ch)| ch.is_ascii_digit()) {
+                        digits.push(digit);
+                    }
+                    let count = digits
+                        .parse()
+                        .map_err(|err| format!("bad count after `*` at {pos}: {err}"))?;
+                    match item {
+                        Item::Repeat(items, 1) => Item::Repeat(items, count),
+                        item => Item::Repeat(vec![item], count),
+                    }
+                }
+                None => item,
+            };
+            items.push(item);
+        }
+    }
+
+    fn join(items: &[Item]) -> String {
+        items
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Applies the items in order and calls `report` after each one. Repeated
+    /// groups skip ahead as soon as the platform comes back to an earlier state.
+    pub fn run(&self, platform: &mut Platform, mut report: impl FnMut(&Item, &Platform)) {
+        for item in &self.items {
+            item.apply(platform);
+            report(item, platform);
+        }
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::join(&self.items))
+    }
+}
+
+/// North load after `cycles` spin cycles, skipping ahead once a platform repeats.
+pub fn solution(input: &str, cycles: usize) -> usize {
+    cycle::nth(&Platform::parse(input), Platform::cycle, cycles).load()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dir, Item, Platform, Program, Tile};
+    use cycle::Cycle;
+
+    /// Moves rocks one cell at a time until nothing moves.
+    fn naive(platform: &Platform, dir: Dir) -> Vec<Vec<Tile>> {
+        let (h, w) = (platform.height() as isize, platform.width() as isize);
+        let mut grid = (0..h)
+            .map(|i| {
+                (0..w)
+                    .map(|j| platform.tile((i as usize, j as usize)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let (di, dj) = match dir {
+            Dir::North => (-1, 0),
+            Dir::West => (0, -1),
+            Dir::South => (1, 0),
+            Dir::East => (0, 1),
+        };
+
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for i in 0..h {
+                for j in 0..w {
+                    let (ni, nj) = (i + di, j + dj);
+                    if (0..h).contains(&ni)
+                        && (0..w).contains(&nj)
+                        && grid[i as usize][j as usize] == Tile::Rounded
+                        && grid[ni as usize][nj as usize] == Tile::Empty
+                    {
+                        grid[i as usize][j as usize] = Tile::Empty;
+                        grid[ni as usize][nj as usize] = Tile::Rounded;
+                        moved = true;
+                    }
+                }
+            }
+        }
+        grid
+    }
+
+    fn tiles(platform: &Platform) -> Vec<Vec<Tile>> {
+        (0..platform.height())
+            .map(|i| {
+                (0..platform.width())
+                    .map(|j| platform.tile((i, j)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tilts_match_naive() {
+        // Short rows cycled out to the width, so a full 128-column row gets
+        // rocks right against both edges.
+        let rows = [
+            "O.#..O.#.#O",
+            "..O..#O..O",
+            "#OO..#....O",
+            "O....#....",
+            ".O.....O#",
+            "OO.#O....O",
+            "...........",
+            "O",
+        ];
+        for width in [1, 10, 63, 64, 65, 127, 128] {
+            let input = rows
+                .iter()
+                .map(|row| row.chars().cycle().take(width).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let platform = Platform::parse(&input);
+
+            for dir in Dir::CYCLE {
+                let mut tilted = platform.clone();
+                tilted.tilt(dir);
+                assert_eq!(tiles(&tilted), naive(&platform, dir), "{width} {dir:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn example_cycle() {
+        let platform = Platform::parse(
+            "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....",
+        );
+        let expected = Cycle {
+            prefix: 3,
+            period: 7,
+        };
+        assert_eq!(cycle::hashed(&platform, Platform::cycle), expected);
+        assert_eq!(cycle::brent(&platform, Platform::cycle), expected);
+    }
+
+    #[test]
+    fn edge_loads() {
+        let platform = Platform::parse("O..\n..#\n.O.");
+        assert_eq!(platform.load_on(Dir::North), 3 + 1);
+        assert_eq!(platform.load_on(Dir::South), 1 + 3);
+        assert_eq!(platform.load_on(Dir::West), 3 + 2);
+        assert_eq!(platform.load_on(Dir::East), 1 + 2);
+    }
+
+    #[test]
+    fn programs() {
+        let program = Program::parse("N  (W S)*3 ((E)*2 N)*1000000000").unwrap();
+        assert_eq!(program.to_string(), "N (W S)*3 ((E)*2 N)*1000000000");
+        assert_eq!(program.items().len(), 3);
+        assert_eq!(
+            Program::parse("N*2").unwrap().items(),
+            [Item::Repeat(vec![Item::Tilt(Dir::North)], 2)]
+        );
+        assert!(Program::parse("N X").is_err());
+        assert!(Program::parse("(N W").is_err());
+        assert!(Program::parse("N)").is_err());
+        assert!(Program::parse("(N)*").is_err());
+    }
+
+    #[test]
+    fn run_program() {
+        let input = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+        let mut platform = Platform::parse(input);
+        let mut loads = vec![];
+        Program::parse("N (N W S E)*1000000000 W")
+            .unwrap()
+            .run(&mut platform, |item, platform| {
+                loads.push((item.to_string(), platform.load()))
+            });
+        assert_eq!(
+            loads,
+            [
+                ("N".to_string(), 136),
+                ("(N W S E)*1000000000".to_string(), 64),
+                ("W".to_string(), 64)
+            ]
+        );
+        assert_eq!(
+            platform.to_string(),
+            "\
+.....#....
+....#O...#
+.....##...
+...#......
+OOO.....#.
+O.#O...#.#
+O....#O...
+OOOO......
+#....###O.
+#OOO.#OO..
+"
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let input = "O....#....\nO.OO#....#\n.....##...\n";
+        assert_eq!(Platform::parse(input).to_string(), input);
+    }
+}
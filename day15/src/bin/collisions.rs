@@ -0,0 +1,46 @@
+use day15::{colliding, collisions_of_len, distribution, hash, labels};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else {
+        eprintln!("usage: collisions <input> [label]");
+        std::process::exit(1);
+    };
+    let input = std::fs::read_to_string(path).unwrap();
+    let labels = labels(&input);
+
+    let boxes = distribution(labels.iter().copied());
+    let used = boxes.iter().filter(|n| **n > 0).count();
+    let fullest = (0..boxes.len()).max_by_key(|id| boxes[*id]).unwrap();
+    // With random boxes a label would expect this many others in its box.
+    let expected = (labels.len() as f64 - 1.0) / boxes.len() as f64;
+    let shared = labels.iter().filter(|label| boxes[hash(label)] > 1).count();
+    println!("{} labels in {used} of {} boxes", labels.len(), boxes.len());
+    println!("box {fullest} holds {} labels", boxes[fullest]);
+    println!("{shared} labels share their box, {expected:.2} neighbours per label expected");
+
+    let mut sizes = vec![0; boxes.iter().max().unwrap() + 1];
+    for n in &boxes {
+        sizes[*n] += 1;
+    }
+    for (size, count) in sizes.iter().enumerate() {
+        println!("{count:>4} boxes with {size} labels");
+    }
+
+    if let Some(target) = args.next() {
+        println!();
+        println!("{target} goes in box {}", hash(&target));
+        println!(
+            "shares it with: {:?}",
+            colliding(labels.iter().copied(), &target)
+        );
+        for len in 1..=3 {
+            let found = collisions_of_len(&target, len).collect::<Vec<_>>();
+            println!(
+                "{} lowercase labels of length {len} collide, e.g. {:?}",
+                found.len(),
+                &found[..found.len().min(5)]
+            );
+        }
+    }
+}
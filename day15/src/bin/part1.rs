@@ -1,12 +1,6 @@
-fn hash(input: &str) -> u32 {
-    input
-        .trim()
-        .chars()
-        .map(|ch| ch as u8)
-        .fold(0, |acc, v| ((acc + v as u32) * 17) % 256)
-}
+use day15::hash;
 
-fn solution(input: &str) -> u32 {
+fn solution(input: &str) -> usize {
     input.split(',').map(hash).sum()
 }
 
@@ -1,120 +1,20 @@
-fn split3(input: &str, id: usize) -> (&str, char, &str) {
-    (&input[..id], input.as_bytes()[id] as char, &input[id + 1..])
-}
-
-struct Action {
-    id: usize,
-    op: Oper,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Oper {
-    Remove,
-    Add(usize),
-}
-
-impl Action {
-    fn from_ret(input: &str) -> (Self, String) {
-        let pos = input.find('-').unwrap_or(0) + input.find('=').unwrap_or(0);
-        let (label, code, num) = split3(input, pos);
-
-        let id = hash(label);
-
-        (
-            match code {
-                '-' => Action {
-                    id,
-                    op: Oper::Remove,
-                },
-                '=' => Action {
-                    id,
-                    op: Oper::Add(num.parse::<usize>().unwrap()),
-                },
-                _ => panic!(),
-            },
-            label.to_owned(),
-        )
-    }
-}
-
-#[derive(Clone, Debug)]
-struct LensBox(Vec<Lens>);
-
-#[derive(Clone, Debug)]
-struct Lens {
-    label: String,
-    power: usize,
-}
-
-impl LensBox {
-    fn new() -> Self {
-        Self(Vec::new())
-    }
-    fn remove(&mut self, label: String) {
-        self.0.retain(|lens| lens.label != label);
-    }
-    fn add(&mut self, label: String, power: usize) {
-        if let Some((id, _)) = self.0.iter().enumerate().find(
-            |(
-                _,
-                Lens {
-                    label: lens_label, ..
-                },
-            )| *lens_label == label,
-        ) {
-            self.0[id].power = power;
-        } else {
-            self.0.push(Lens { label, power });
-        }
-    }
-    fn calc(self) -> usize {
-        self.0
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, curr| acc + (curr.0 + 1) * curr.1.power)
-    }
-}
-
-fn hash(input: &str) -> usize {
-    input
-        .trim()
-        .chars()
-        .map(|ch| ch as u8)
-        .fold(0, |acc, v| ((acc + v as usize) * 17) % 256)
-}
-
 fn solution(input: &str) -> usize {
-    let mut boxes = vec![LensBox::new(); 256];
-
-    input
-        .trim()
-        .split(',')
-        .map(Action::from_ret)
-        .for_each(|(Action { id, op }, label)| {
-            // println!("id: {}, op: {:?}", id, op);
-            match op {
-                Oper::Remove => boxes[id].remove(label),
-                Oper::Add(power) => boxes[id].add(label, power),
-            }
-            // println!("{:#?}", &boxes[..4]);
-        });
-
-    boxes
-        .into_iter()
-        .enumerate()
-        .map(|(i, lens_box)| lens_box.calc() * (i + 1))
-        .sum()
+    day15::solution(input)
 }
 
 fn main() {
     let input = include_str!("../../input1.txt");
+    if std::env::args().any(|arg| arg == "--trace") {
+        println!("{}", day15::walkthrough(input));
+    }
     let res = solution(input);
     println!("{res}");
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{hash, solution};
+    use crate::solution;
+    use day15::hash;
 
     #[test]
     fn test() {
@@ -0,0 +1,394 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    hash::{BuildHasher, BuildHasherDefault, Hash, Hasher},
+};
+
+/// The HASH algorithm: add the byte, multiply by 17, keep the remainder
+/// modulo 256. Only 256 outcomes, so `std` maps using it collide a lot.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct HolidayHasher(u8);
+
+impl Hasher for HolidayHasher {
+    fn finish(&self) -> u64 {
+        self.0 as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.wrapping_add(byte).wrapping_mul(17);
+        }
+    }
+}
+
+pub type BuildHolidayHasher = BuildHasherDefault<HolidayHasher>;
+
+/// A label that hashes to its HASH value. `str` feeds a terminating `0xff`
+/// byte to the hasher, which would put labels in a different box.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Label<'a>(pub &'a str);
+
+impl Hash for Label<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.0.as_bytes());
+    }
+}
+
+pub fn hash(input: &str) -> usize {
+    BuildHolidayHasher::default().hash_one(Label(input.trim())) as usize
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Oper {
+    Remove,
+    Add(usize),
+}
+
+/// One step of the initialization sequence, like `rn=1` or `cm-`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Action<'a> {
+    pub label: &'a str,
+    pub op: Oper,
+}
+
+impl<'a> Action<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let input = input.trim();
+        if let Some(label) = input.strip_suffix('-') {
+            return Self {
+                label,
+                op: Oper::Remove,
+            };
+        }
+        let (label, num) = input.split_once('=').unwrap();
+        Self {
+            label,
+            op: Oper::Add(num.parse().unwrap()),
+        }
+    }
+}
+
+/// 256 boxes picked by `hash` of the label. Each box keeps its entries in
+/// insertion order; replacing a value keeps the entry's slot.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HolidayMap<V> {
+    boxes: Vec<Vec<(String, V)>>,
+}
+
+impl<V> HolidayMap<V> {
+    pub fn new() -> Self {
+        Self {
+            boxes: (0..256).map(|_| vec![]).collect(),
+        }
+    }
+
+    fn slot(&self, label: &str) -> (usize, Option<usize>) {
+        let id = hash(label);
+        let slot = self.boxes[id].iter().position(|(key, _)| key == label);
+        (id, slot)
+    }
+
+    /// Returns the old value if the label was already there.
+    pub fn insert(&mut self, label: &str, value: V) -> Option<V> {
+        match self.slot(label) {
+            (id, Some(slot)) => Some(std::mem::replace(&mut self.boxes[id][slot].1, value)),
+            (id, None) => {
+                self.boxes[id].push((label.to_owned(), value));
+                None
+            }
+        }
+    }
+
+    /// Later entries of the box move forward to close the gap.
+    pub fn remove(&mut self, label: &str) -> Option<V> {
+        let (id, slot) = self.slot(label);
+        slot.map(|slot| self.boxes[id].remove(slot).1)
+    }
+
+    pub fn get(&self, label: &str) -> Option<&V> {
+        let (id, slot) = self.slot(label);
+        slot.map(|slot| &self.boxes[id][slot].1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.boxes.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.iter().all(Vec::is_empty)
+    }
+
+    /// Entries of box `id`, front to back.
+    pub fn lens_box(&self, id: usize) -> impl Iterator<Item = (&str, &V)> {
+        self.boxes[id]
+            .iter()
+            .map(|(label, value)| (label.as_str(), value))
+    }
+
+    /// All entries as `(box, label, value)`, box by box.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &str, &V)> {
+        (0..self.boxes.len()).flat_map(move |id| {
+            self.lens_box(id)
+                .map(move |(label, value)| (id, label, value))
+        })
+    }
+}
+
+impl<V> Default for HolidayMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HolidayMap<usize> {
+    pub fn apply(&mut self, action: Action) {
+        match action.op {
+            Oper::Remove => {
+                self.remove(action.label);
+            }
+            Oper::Add(power) => {
+                self.insert(action.label, power);
+            }
+        }
+    }
+
+    /// Sum of `(box + 1) * (slot + 1) * focal length` over all lenses.
+    pub fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .map(|(i, lens_box)| {
+                lens_box
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (slot, (_, power))| acc + (slot + 1) * power)
+                    * (i + 1)
+            })
+            .sum()
+    }
+}
+
+/// Non-empty boxes as in the puzzle, one per line: `Box 0: [rn 1] [cm 2]`.
+impl<V: fmt::Debug> fmt::Debug for HolidayMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (id, lens_box) in self.boxes.iter().enumerate() {
+            if lens_box.is_empty() {
+                continue;
+            }
+            write!(f, "Box {id}:")?;
+            for (label, value) in lens_box {
+                write!(f, " [{label} {value:?}]")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Applies the comma separated steps in order, calling `trace` after each one.
+pub fn run(input: &str, mut trace: impl FnMut(&str, &HolidayMap<usize>)) -> HolidayMap<usize> {
+    let mut map = HolidayMap::new();
+    for step in input.trim().split(',') {
+        map.apply(Action::parse(step));
+        trace(step, &map);
+    }
+    map
+}
+
+/// The state after every step, in the format of the puzzle's walkthrough.
+pub fn walkthrough(input: &str) -> String {
+    let mut res = vec![];
+    run(input, |step, map| {
+        res.push(format!("After \"{step}\":\n{map:?}"))
+    });
+    res.join("\n")
+}
+
+/// Distinct labels of the initialization sequence, in order of first use.
+pub fn labels(input: &str) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    input
+        .trim()
+        .split(',')
+        .map(|step| Action::parse(step).label)
+        .filter(|label| seen.insert(*label))
+        .collect()
+}
+
+/// Number of distinct labels landing in every box.
+pub fn distribution<'a>(labels: impl IntoIterator<Item = &'a str>) -> Vec<usize> {
+    let mut res = vec![0; 256];
+    for label in labels.into_iter().collect::<HashSet<_>>() {
+        res[hash(label)] += 1;
+    }
+    res
+}
+
+/// The other labels that land in the same box as `target`.
+pub fn colliding<'a>(labels: impl IntoIterator<Item = &'a str>, target: &str) -> Vec<&'a str> {
+    let id = hash(target);
+    labels
+        .into_iter()
+        .filter(|label| *label != target && hash(label) == id)
+        .collect()
+}
+
+/// Every lowercase label of length `len` that lands in the same box as `target`.
+pub fn collisions_of_len(target: &str, len: usize) -> impl Iterator<Item = String> {
+    let id = hash(target);
+    let target = target.to_owned();
+    (0..26usize.pow(len as u32))
+        .map(move |mut n| {
+            let mut label = vec![b'a'; len];
+            for byte in label.iter_mut().rev() {
+                *byte += (n % 26) as u8;
+                n /= 26;
+            }
+            String::from_utf8(label).unwrap()
+        })
+        .filter(move |label| *label != target && hash(label) == id)
+}
+
+pub fn solution(input: &str) -> usize {
+    run(input, |_, _| {}).focusing_power()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        colliding, collisions_of_len, distribution, hash, labels, run, walkthrough,
+        BuildHolidayHasher, HolidayMap, Label,
+    };
+    use std::{collections::HashMap, hash::BuildHasher};
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn map_operations() {
+        let mut map = HolidayMap::new();
+        assert!(map.is_empty());
+        assert_eq!(map.insert("rn", 'a'), None);
+        assert_eq!(map.insert("cm", 'b'), None);
+        assert_eq!(map.insert("qp", 'c'), None);
+        assert_eq!(map.insert("rn", 'd'), Some('a'));
+        assert_eq!(map.get("rn"), Some(&'d'));
+        assert_eq!(map.get("ot"), None);
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map.lens_box(0).collect::<Vec<_>>(),
+            [("rn", &'d'), ("cm", &'b')]
+        );
+        assert_eq!(map.remove("rn"), Some('d'));
+        assert_eq!(map.remove("rn"), None);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(0, "cm", &'b'), (1, "qp", &'c')]
+        );
+    }
+
+    #[test]
+    fn hasher_matches_hash() {
+        let build = BuildHolidayHasher::default();
+        assert_eq!(build.hash_one(Label("HASH")), 52);
+        for label in labels(EXAMPLE) {
+            assert_eq!(build.hash_one(Label(label)), hash(label) as u64);
+        }
+        // `str` hashes an extra terminator byte.
+        assert_ne!(build.hash_one("rn"), build.hash_one(Label("rn")));
+
+        let mut map = HashMap::with_hasher(build);
+        for step in EXAMPLE.split(',') {
+            match step.split_once('=') {
+                Some((label, num)) => map.insert(Label(label), num.parse::<usize>().unwrap()),
+                None => map.remove(&Label(step.trim_end_matches('-'))),
+            };
+        }
+        assert_eq!(map.len(), 5);
+        assert_eq!(map[&Label("ot")], 7);
+        assert_eq!(map.get(&Label("qp")), None);
+    }
+
+    #[test]
+    fn collisions() {
+        let labels = labels(EXAMPLE);
+        assert_eq!(labels, ["rn", "cm", "qp", "pc", "ot", "ab"]);
+
+        let boxes = distribution(labels.iter().copied());
+        assert_eq!(boxes.iter().sum::<usize>(), 6);
+        assert_eq!((boxes[0], boxes[1], boxes[3]), (2, 1, 3));
+        assert_eq!(colliding(labels.iter().copied(), "pc"), ["ot", "ab"]);
+        assert_eq!(colliding(labels.iter().copied(), "zz"), Vec::<&str>::new());
+
+        assert_eq!(collisions_of_len("rn", 2).collect::<Vec<_>>(), ["cm"]);
+        let found = collisions_of_len("rn", 3).collect::<Vec<_>>();
+        assert!(found.len() > 26);
+        assert!(found.iter().all(|label| hash(label) == 0));
+
+        // Two-letter labels reach every box, but up to four share one.
+        let all = (b'a'..=b'z')
+            .flat_map(|a| (b'a'..=b'z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+            .collect::<Vec<_>>();
+        let boxes = distribution(all.iter().map(String::as_str));
+        assert!(boxes.iter().all(|n| *n > 0));
+        assert_eq!(boxes.iter().max(), Some(&4));
+    }
+
+    #[test]
+    fn debug_format() {
+        let map = run(EXAMPLE, |_, _| {});
+        assert_eq!(
+            format!("{map:?}"),
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]\n"
+        );
+        assert_eq!(map.focusing_power(), 145);
+    }
+
+    #[test]
+    fn puzzle_walkthrough() {
+        let res = walkthrough(EXAMPLE);
+        assert_eq!(
+            res,
+            r#"After "rn=1":
+Box 0: [rn 1]
+
+After "cm-":
+Box 0: [rn 1]
+
+After "qp=3":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After "cm=2":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+
+After "qp-":
+Box 0: [rn 1] [cm 2]
+
+After "pc=4":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4]
+
+After "ot=9":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9]
+
+After "ab=5":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9] [ab 5]
+
+After "pc-":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5]
+
+After "pc=6":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5] [pc 6]
+
+After "ot=7":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 7] [ab 5] [pc 6]
+"#
+        );
+    }
+}
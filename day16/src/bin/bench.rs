@@ -0,0 +1,44 @@
+use day16::{best_placement, parse, Beam, Direction, Field};
+use std::time::Instant;
+
+const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+/// The puzzle example repeated into a square of `size` tiles a side.
+fn generate(size: usize) -> String {
+    let rows = EXAMPLE.lines().collect::<Vec<_>>();
+    (0..size)
+        .map(|i| rows[i % rows.len()].chars().cycle().take(size).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let size = args.next().map_or(100, |arg| arg.parse().unwrap());
+    let budget = args.next().map_or(2, |arg| arg.parse().unwrap());
+    let types = parse(&generate(size));
+    let start = Beam::from(Direction::Right, (0, -1));
+    println!(
+        "{size}x{size}, no extra tiles: {}",
+        Field::from(&types).count(start)
+    );
+
+    for budget in 1..=budget {
+        let begin = Instant::now();
+        let (placed, count) = best_placement(&types, start, budget);
+        let elapsed = begin.elapsed();
+        println!(
+            "{size}x{size}, budget {budget}: {count} with {} placed in {elapsed:.2?}",
+            placed.len()
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use day16::{best_placement, parse, Beam, Direction, Field};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else {
+        eprintln!("usage: mirrors <input> [budget]");
+        std::process::exit(1);
+    };
+    let budget = args.next().map_or(1, |arg| arg.parse().unwrap());
+
+    let input = std::fs::read_to_string(path).unwrap();
+    let mut types = parse(&input);
+    let start = Beam::from(Direction::Right, (0, -1));
+    println!("without extra tiles: {}", Field::from(&types).count(start));
+
+    let (placed, count) = best_placement(&types, start, budget);
+    for placement in &placed {
+        let (i, j) = placement.pos;
+        println!("put {} at row {i}, column {j}", char::from(placement.tile));
+        types[i][j] = placement.tile;
+    }
+    println!("energized: {count}");
+
+    let field = Field::from(&types);
+    print!("{}", field.render(&field.arrivals(start)));
+}
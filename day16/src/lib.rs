@@ -0,0 +1,960 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum TileType {
+    Empty,
+    MirrorF,
+    MirrorB,
+    SplitterH,
+    SplitterV,
+}
+
+impl From<TileType> for char {
+    fn from(value: TileType) -> Self {
+        match value {
+            TileType::Empty => '.',
+            TileType::MirrorF => '/',
+            TileType::MirrorB => '\\',
+            TileType::SplitterH => '-',
+            TileType::SplitterV => '|',
+        }
+    }
+}
+
+impl From<char> for TileType {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => Self::Empty,
+            '/' => Self::MirrorF,
+            '\\' => Self::MirrorB,
+            '-' => Self::SplitterH,
+            '|' => Self::SplitterV,
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub struct Beam {
+    pub dir: Direction,
+    pub pos: (i32, i32),
+}
+
+impl Beam {
+    pub fn from(dir: Direction, pos: (i32, i32)) -> Self {
+        Self { dir, pos }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// In the order beams are numbered in on each tile.
+    pub const ALL: [Direction; 4] = [
+        Direction::Top,
+        Direction::Bottom,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Top => '^',
+            Direction::Bottom => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+/// When a beam first reaches a tile, and every direction beams travel in
+/// as they enter it, in the order they first do.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Arrival {
+    pub step: usize,
+    pub dirs: Vec<Direction>,
+}
+
+/// The directions a beam going `dir` leaves `tile` in.
+fn turns(dir: Direction, tile: TileType) -> &'static [Direction] {
+    use Direction as Dir;
+    match (dir, tile) {
+        (Dir::Top, TileType::MirrorF) => &[Dir::Right],
+        (Dir::Bottom, TileType::MirrorF) => &[Dir::Left],
+        (Dir::Right, TileType::MirrorF) => &[Dir::Top],
+        (Dir::Left, TileType::MirrorF) => &[Dir::Bottom],
+        (Dir::Top, TileType::MirrorB) => &[Dir::Left],
+        (Dir::Bottom, TileType::MirrorB) => &[Dir::Right],
+        (Dir::Right, TileType::MirrorB) => &[Dir::Bottom],
+        (Dir::Left, TileType::MirrorB) => &[Dir::Top],
+        (Dir::Left | Dir::Right, TileType::SplitterV) => &[Dir::Bottom, Dir::Top],
+        (Dir::Top | Dir::Bottom, TileType::SplitterH) => &[Dir::Left, Dir::Right],
+        (Dir::Top, _) => &[Dir::Top],
+        (Dir::Bottom, _) => &[Dir::Bottom],
+        (Dir::Left, _) => &[Dir::Left],
+        (Dir::Right, _) => &[Dir::Right],
+    }
+}
+
+pub fn parse(input: &str) -> Vec<Vec<TileType>> {
+    input
+        .lines()
+        .map(|line| line.chars().map(TileType::from).collect())
+        .collect()
+}
+
+pub struct Field<'a> {
+    field: &'a [Vec<TileType>],
+    visits: Vec<Vec<bool>>,
+    width: i32,
+    height: i32,
+}
+
+impl<'a> Field<'a> {
+    pub fn from(field: &'a [Vec<TileType>]) -> Self {
+        let width = field[0].len();
+        let height = field.len();
+        Field {
+            field,
+            visits: vec![vec![false; width]; height],
+            width: width as i32,
+            height: height as i32,
+        }
+    }
+
+    /// Beams entering from outside along every edge, one per edge tile.
+    pub fn entries(&self) -> Vec<Beam> {
+        let (width, height) = (self.width, self.height);
+        (0..height)
+            .map(|y| Beam::from(Direction::Right, (y, -1)))
+            .chain((0..height).map(|y| Beam::from(Direction::Left, (y, width))))
+            .chain((0..width).map(|x| Beam::from(Direction::Bottom, (-1, x))))
+            .chain((0..width).map(|x| Beam::from(Direction::Top, (height, x))))
+            .collect()
+    }
+
+    /// Moves the beam one tile and turns or splits it there. Beams leaving
+    /// the contraption are gone.
+    pub fn step(&self, Beam { dir, mut pos }: Beam) -> Vec<Beam> {
+        use Direction as Dir;
+        match dir {
+            Dir::Top => pos.0 -= 1,
+            Dir::Bottom => pos.0 += 1,
+            Dir::Left => pos.1 -= 1,
+            Dir::Right => pos.1 += 1,
+        }
+        if !(0..self.height).contains(&pos.0) || !(0..self.width).contains(&pos.1) {
+            return vec![];
+        }
+
+        turns(dir, self.field[pos.0 as usize][pos.1 as usize])
+            .iter()
+            .map(|&dir| Beam { dir, pos })
+            .collect()
+    }
+
+    pub fn count(mut self, start: Beam) -> usize {
+        let mut beams = vec![start];
+
+        let mut poses: HashSet<Beam> = HashSet::new();
+
+        while !beams.is_empty() {
+            beams.retain(|beam| poses.insert(*beam));
+
+            beams = beams.into_iter().flat_map(|beam| self.step(beam)).collect();
+            for Beam { pos, .. } in &beams {
+                self.visits[pos.0 as usize][pos.1 as usize] = true;
+            }
+        }
+
+        self.visits.iter().flatten().filter(|visit| **visit).count()
+    }
+
+    /// Arrivals of the beam from `start` on every tile; step 1 is the first
+    /// tile it enters. Tiles it never reaches are `None`.
+    pub fn arrivals(&self, start: Beam) -> Vec<Vec<Option<Arrival>>> {
+        let mut res = vec![vec![None; self.width as usize]; self.height as usize];
+        let mut beams = vec![start];
+        let mut poses: HashSet<Beam> = HashSet::new();
+        let mut step = 0;
+
+        while !beams.is_empty() {
+            step += 1;
+            beams.retain(|beam| poses.insert(*beam));
+
+            let mut next = vec![];
+            for beam in beams {
+                for moved in self.step(beam) {
+                    let arrival = res[moved.pos.0 as usize][moved.pos.1 as usize]
+                        .get_or_insert_with(|| Arrival { step, dirs: vec![] });
+                    if !arrival.dirs.contains(&beam.dir) {
+                        arrival.dirs.push(beam.dir);
+                    }
+                    next.push(moved);
+                }
+            }
+            beams = next;
+        }
+
+        res
+    }
+
+    /// The contraption as in the puzzle: empty tiles a beam crosses show its
+    /// direction, or the number of directions when several beams cross.
+    pub fn render(&self, arrivals: &[Vec<Option<Arrival>>]) -> String {
+        let mut res = String::new();
+        for (row, arrivals) in self.field.iter().zip(arrivals) {
+            for (tile, arrival) in row.iter().zip(arrivals) {
+                res.push(match (tile, arrival) {
+                    (TileType::Empty, Some(Arrival { dirs, .. })) if dirs.len() == 1 => {
+                        dirs[0].arrow()
+                    }
+                    (TileType::Empty, Some(Arrival { dirs, .. })) => {
+                        char::from_digit(dirs.len() as u32, 10).unwrap()
+                    }
+                    (tile, _) => char::from(*tile),
+                });
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    /// Index of a beam inside the contraption: four per tile, in reading order.
+    fn state(&self, Beam { dir, pos }: Beam) -> usize {
+        ((pos.0 * self.width + pos.1) as usize) * 4 + dir as usize
+    }
+
+    /// Energized tile counts for every entry of `entries`, from one pass over
+    /// the beam graph. Beams that reach each other form strongly connected
+    /// components; each component's tiles are those of its own beams plus
+    /// those of every component it leads to, and a component's tile set is
+    /// dropped once every beam and entry leading into it has used it.
+    pub fn energized_all(&self) -> Vec<(Beam, usize)> {
+        let tiles = (self.width * self.height) as usize;
+        let (comp, members, succs) = self.components();
+        let count = members.len();
+        let mut refs = vec![0; count];
+        for next in succs.iter().flatten() {
+            refs[*next] += 1;
+        }
+
+        let entries = self.entries();
+        let mut starts = vec![vec![]; entries.len()];
+        let mut waiting = vec![vec![]; count];
+        for (i, entry) in entries.iter().enumerate() {
+            starts[i] = self
+                .step(*entry)
+                .into_iter()
+                .map(|next| comp[self.state(next)])
+                .collect::<Vec<_>>();
+            for &start in &starts[i] {
+                refs[start] += 1;
+            }
+            waiting[*starts[i].iter().max().unwrap()].push(i);
+        }
+
+        // Tarjan numbers components so that every edge leads to a lower number.
+        let mut sets: Vec<Option<Tiles>> = vec![None; count];
+        let mut res = vec![0; entries.len()];
+        let release = |sets: &mut Vec<Option<Tiles>>, refs: &mut Vec<usize>, c: usize| {
+            refs[c] -= 1;
+            if refs[c] == 0 {
+                sets[c] = None;
+            }
+        };
+        for c in 0..count {
+            let mut set = Tiles::new(tiles);
+            for &tile in &members[c] {
+                set.insert(tile);
+            }
+            for &next in &succs[c] {
+                set.union_with(sets[next].as_ref().unwrap());
+            }
+            for &next in &succs[c] {
+                release(&mut sets, &mut refs, next);
+            }
+            sets[c] = Some(set);
+
+            for &i in &waiting[c] {
+                let mut set = Tiles::new(tiles);
+                for &start in &starts[i] {
+                    set.union_with(sets[start].as_ref().unwrap());
+                }
+                res[i] = set.count();
+                for &start in &starts[i] {
+                    release(&mut sets, &mut refs, start);
+                }
+            }
+            if refs[c] == 0 {
+                sets[c] = None;
+            }
+        }
+
+        entries.into_iter().zip(res).collect()
+    }
+
+    /// Beams that reach each other, as components of the beam graph. Returns
+    /// the component of every state, and for each component the tiles of its
+    /// beams and the components they lead to.
+    fn components(&self) -> (Vec<usize>, Vec<Vec<usize>>, Vec<Vec<usize>>) {
+        let tiles = (self.width * self.height) as usize;
+        let beam = |state: usize| {
+            let tile = (state / 4) as i32;
+            let dir = Direction::ALL[state % 4];
+            Beam::from(dir, (tile / self.width, tile % self.width))
+        };
+        let adj = (0..tiles * 4)
+            .map(|state| {
+                self.step(beam(state))
+                    .into_iter()
+                    .map(|next| self.state(next))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let (comp, count) = tarjan(&adj);
+
+        let mut members = vec![vec![]; count];
+        let mut succs = vec![vec![]; count];
+        for (state, next) in adj.iter().enumerate() {
+            members[comp[state]].push(state / 4);
+            for &next in next {
+                if comp[next] != comp[state] {
+                    succs[comp[state]].push(comp[next]);
+                }
+            }
+        }
+        (comp, members, succs)
+    }
+
+    /// For every state, how many tiles outside `known` the beam from it
+    /// reaches, from one pass over the components like `energized_all`.
+    fn reach_beyond(&self, known: &[bool]) -> Vec<usize> {
+        let tiles = (self.width * self.height) as usize;
+        let (comp, members, succs) = self.components();
+        let count = members.len();
+        let mut refs = vec![0; count];
+        for next in succs.iter().flatten() {
+            refs[*next] += 1;
+        }
+
+        let mut sets: Vec<Option<Tiles>> = vec![None; count];
+        let mut beyond = vec![0; count];
+        for c in 0..count {
+            let mut set = Tiles::new(tiles);
+            for &tile in members[c].iter().filter(|&&tile| !known[tile]) {
+                set.insert(tile);
+            }
+            for &next in &succs[c] {
+                set.union_with(sets[next].as_ref().unwrap());
+            }
+            for &next in &succs[c] {
+                refs[next] -= 1;
+                if refs[next] == 0 {
+                    sets[next] = None;
+                }
+            }
+            beyond[c] = set.count();
+            if refs[c] > 0 {
+                sets[c] = Some(set);
+            }
+        }
+
+        comp.into_iter().map(|c| beyond[c]).collect()
+    }
+
+    /// The entry that energizes the most tiles; ties go to the earlier entry.
+    pub fn best_entry(&self) -> (Beam, usize) {
+        self.energized_all()
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .unwrap()
+    }
+}
+
+/// An extra tile put on an empty cell.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub struct Placement {
+    pub pos: (usize, usize),
+    pub tile: TileType,
+}
+
+/// Places up to `budget` extra mirrors or splitters on empty tiles to energize
+/// as many tiles as possible from `start`. Ties go to fewer placements.
+/// Returns the placements and the count.
+///
+/// A placed tile only sends the beams already arriving on it somewhere new,
+/// so the search keeps every beam reached so far and follows just the new
+/// ones. Beams a placement cuts off stay counted, which makes the count an
+/// upper bound; the contraption is only simulated in full where that bound
+/// beats the best count so far.
+pub fn best_placement(
+    types: &[Vec<TileType>],
+    start: Beam,
+    budget: usize,
+) -> (Vec<Placement>, usize) {
+    let mut search = Search::new(types, start);
+    // Fewer placements first, so only a strictly better count replaces the best.
+    for size in 1..=budget {
+        search.place(size, 0, &mut vec![]);
+    }
+    search.best
+}
+
+/// Every direction a beam can leave `tile` in.
+fn leaving(tile: TileType) -> &'static [Direction] {
+    use Direction as Dir;
+    match tile {
+        TileType::SplitterH => &[Dir::Left, Dir::Right],
+        TileType::SplitterV => &[Dir::Top, Dir::Bottom],
+        _ => &Dir::ALL,
+    }
+}
+
+const PLACEABLE: [TileType; 4] = [
+    TileType::MirrorF,
+    TileType::MirrorB,
+    TileType::SplitterH,
+    TileType::SplitterV,
+];
+
+/// Beams are states numbered as in `Field::state`.
+struct Search {
+    /// The tiles, row by row, with the placements so far.
+    grid: Vec<TileType>,
+    width: usize,
+    start: Beam,
+    /// Beams still to follow, kept to save allocating it on every put.
+    beams: Vec<usize>,
+    /// Tiles the beam crosses without placements.
+    plain: Vec<bool>,
+    /// Beams reached so far, how many of them are on each tile, and the
+    /// ones reached since the plain beam, in order.
+    seen: Vec<bool>,
+    crossed: Vec<usize>,
+    log: Vec<usize>,
+    /// Tiles crossed by the plain beam, and tiles crossed since.
+    base: usize,
+    extra: usize,
+    /// Every placement on an empty tile, by decreasing gain.
+    candidates: Vec<Candidate>,
+    /// For each tile, the candidate putting each placeable tile there.
+    index: Vec<[Option<usize>; 4]>,
+    /// For each tile, the candidates whose own new beams cross it.
+    crossing: Vec<Vec<usize>>,
+    /// Sum of `reach` over the tiles placed so far.
+    spent: usize,
+    /// For every beam, the tiles off the plain beam it reaches without
+    /// placements.
+    beyond: Vec<usize>,
+    best: (Vec<Placement>, usize),
+}
+
+#[derive(Clone, Copy)]
+struct Candidate {
+    placement: Placement,
+    /// Tiles it adds on its own.
+    gain: usize,
+    /// Tiles off the plain beam that beams leaving it in any direction reach
+    /// without other placements. Whatever beams leaving placed tiles reach
+    /// on the plain grid bounds what any set of placements adds.
+    reach: usize,
+}
+
+impl Search {
+    fn new(types: &[Vec<TileType>], start: Beam) -> Self {
+        let (width, height) = (types[0].len(), types.len());
+        let mut search = Search {
+            grid: types.concat(),
+            width,
+            start,
+            beams: vec![],
+            plain: vec![],
+            seen: vec![false; width * height * 4],
+            crossed: vec![0; width * height],
+            log: vec![],
+            base: 0,
+            extra: 0,
+            candidates: vec![],
+            index: vec![[None; 4]; width * height],
+            crossing: vec![vec![]; width * height],
+            spent: 0,
+            beyond: vec![],
+            best: (vec![], 0),
+        };
+        search.spread(&mut search.first());
+        search.plain = search.crossed.iter().map(|&n| n > 0).collect();
+        (search.base, search.extra) = (search.extra, 0);
+        search.log.clear();
+        search.best.1 = search.base;
+        let beyond = Field::from(types).reach_beyond(&search.plain);
+
+        let mut candidates = vec![];
+        for (i, row) in types.iter().enumerate() {
+            for (j, _) in row
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| **t == TileType::Empty)
+            {
+                for tile in PLACEABLE {
+                    let placement = Placement { pos: (i, j), tile };
+                    let reach = leaving(tile)
+                        .iter()
+                        .map(|&dir| beyond[(i * width + j) * 4 + dir as usize])
+                        .sum();
+                    let mark = search.put(placement);
+                    candidates.push(Candidate {
+                        placement,
+                        gain: search.extra,
+                        reach,
+                    });
+                    search.take(placement, mark);
+                }
+            }
+        }
+        candidates.sort_by_key(|candidate| Reverse(candidate.gain));
+
+        for (k, &Candidate { placement, .. }) in candidates.iter().enumerate() {
+            let (i, j) = placement.pos;
+            let kind = PLACEABLE.iter().position(|&t| t == placement.tile).unwrap();
+            search.index[i * width + j][kind] = Some(k);
+
+            let mark = search.put(placement);
+            let mut tiles = search.log[mark..]
+                .iter()
+                .map(|state| state / 4)
+                .collect::<Vec<_>>();
+            tiles.sort_unstable();
+            tiles.dedup();
+            for tile in tiles {
+                search.crossing[tile].push(k);
+            }
+            search.take(placement, mark);
+        }
+        search.candidates = candidates;
+        search.beyond = beyond;
+        search
+    }
+
+    /// Puts down `left` more tiles on top of `placed`. Tiles on the plain
+    /// beam are taken from `candidates[from..]`, so each set of them comes up
+    /// in one order only.
+    fn place(&mut self, left: usize, from: usize, placed: &mut Vec<Placement>) {
+        if left == 0 {
+            if self.base + self.extra > self.best.1 {
+                let count = self.energized();
+                if count > self.best.1 {
+                    self.best = (placed.clone(), count);
+                }
+            }
+            return;
+        }
+
+        for k in from..self.candidates.len() {
+            let candidate = self.candidates[k];
+            // A last placement adds at most its gain unless it is one of the
+            // others below, and gains only shrink from here.
+            if left == 1 && self.base + self.extra + candidate.gain <= self.best.1 {
+                break;
+            }
+            let (i, j) = candidate.placement.pos;
+            let tile = i * self.width + j;
+            if self.plain[tile] && self.grid[tile] == TileType::Empty {
+                self.descend(candidate, left, k + 1, placed);
+            }
+        }
+
+        // Placements on tiles the new beams cross, and placements whose own
+        // new beams cross a placed tile, can add more than their gain. With
+        // one tile placed, the latter also come up the other way round: their
+        // tile goes first and this one is on its new beams.
+        let crossing = if placed.len() > 1 { &placed[..] } else { &[] };
+        let mut others = self
+            .log
+            .iter()
+            .flat_map(|state| self.index[state / 4].iter().flatten())
+            .chain(
+                crossing
+                    .iter()
+                    .flat_map(|Placement { pos: (i, j), .. }| &self.crossing[i * self.width + j]),
+            )
+            .copied()
+            .collect::<Vec<_>>();
+        others.sort_unstable();
+        others.dedup();
+        for k in others {
+            let candidate = self.candidates[k];
+            let (i, j) = candidate.placement.pos;
+            // Beams already reached have been followed, so only the other
+            // ways out of the tile can add anything.
+            let at = (i * self.width + j) * 4;
+            let reach: usize = leaving(candidate.placement.tile)
+                .iter()
+                .map(|&dir| at + dir as usize)
+                .filter(|&state| !self.seen[state])
+                .map(|state| self.beyond[state])
+                .sum();
+            if left == 1 && self.base + self.extra + self.spent + reach <= self.best.1 {
+                continue;
+            }
+            if self.grid[i * self.width + j] == TileType::Empty {
+                self.descend(candidate, left, from, placed);
+            }
+        }
+    }
+
+    fn descend(
+        &mut self,
+        candidate: Candidate,
+        left: usize,
+        from: usize,
+        placed: &mut Vec<Placement>,
+    ) {
+        let mark = self.put(candidate.placement);
+        placed.push(candidate.placement);
+        self.spent += candidate.reach;
+        self.place(left - 1, from, placed);
+        self.spent -= candidate.reach;
+        placed.pop();
+        self.take(candidate.placement, mark);
+    }
+
+    /// Puts `placement` down and follows the beams arriving on it from there.
+    /// Returns where the new beams start in `log`.
+    fn put(&mut self, Placement { pos: (i, j), tile }: Placement) -> usize {
+        let mark = self.log.len();
+        self.grid[i * self.width + j] = tile;
+        let at = (i * self.width + j) * 4;
+        let mut beams = std::mem::take(&mut self.beams);
+        beams.extend(
+            Direction::ALL
+                .into_iter()
+                .filter(|&dir| self.seen[at + dir as usize])
+                .flat_map(|dir| turns(dir, tile))
+                .map(|&dir| at + dir as usize),
+        );
+        self.spread(&mut beams);
+        self.beams = beams;
+        mark
+    }
+
+    fn take(&mut self, Placement { pos: (i, j), .. }: Placement, mark: usize) {
+        for state in self.log.drain(mark..) {
+            self.seen[state] = false;
+            self.crossed[state / 4] -= 1;
+            if self.crossed[state / 4] == 0 {
+                self.extra -= 1;
+            }
+        }
+        self.grid[i * self.width + j] = TileType::Empty;
+    }
+
+    fn spread(&mut self, beams: &mut Vec<usize>) {
+        while let Some(state) = beams.pop() {
+            if !self.seen[state] {
+                self.seen[state] = true;
+                self.log.push(state);
+                self.crossed[state / 4] += 1;
+                if self.crossed[state / 4] == 1 {
+                    self.extra += 1;
+                }
+                self.next(state, beams);
+            }
+        }
+    }
+
+    /// The beams the start turns into on the first tile.
+    fn first(&self) -> Vec<usize> {
+        let rows = self.grid.chunks(self.width).map(<[_]>::to_vec);
+        Field::from(&rows.collect::<Vec<_>>())
+            .step(self.start)
+            .into_iter()
+            .map(|Beam { dir, pos }| {
+                (pos.0 as usize * self.width + pos.1 as usize) * 4 + dir as usize
+            })
+            .collect()
+    }
+
+    /// The beams `state` turns into on the next tile.
+    fn next(&self, state: usize, beams: &mut Vec<usize>) {
+        let (tile, dir) = (state / 4, Direction::ALL[state % 4]);
+        let next = match dir {
+            Direction::Top => tile.checked_sub(self.width),
+            Direction::Bottom => Some(tile + self.width).filter(|&next| next < self.grid.len()),
+            Direction::Left => (tile % self.width > 0).then(|| tile - 1),
+            Direction::Right => ((tile + 1) % self.width > 0).then_some(tile + 1),
+        };
+        if let Some(next) = next {
+            beams.extend(
+                turns(dir, self.grid[next])
+                    .iter()
+                    .map(|&dir| next * 4 + dir as usize),
+            );
+        }
+    }
+
+    /// Tiles energized with the tiles placed so far.
+    fn energized(&self) -> usize {
+        let mut seen = vec![false; self.seen.len()];
+        let mut tiles = vec![false; self.crossed.len()];
+        let mut beams = self.first();
+        while let Some(state) = beams.pop() {
+            if !seen[state] {
+                seen[state] = true;
+                tiles[state / 4] = true;
+                self.next(state, &mut beams);
+            }
+        }
+        tiles.iter().filter(|&&tile| tile).count()
+    }
+}
+
+/// A set of tiles, one bit each.
+#[derive(Clone)]
+struct Tiles(Vec<u64>);
+
+impl Tiles {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn insert(&mut self, tile: usize) {
+        self.0[tile / 64] |= 1 << (tile % 64);
+    }
+
+    fn union_with(&mut self, other: &Tiles) {
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            *word |= other;
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+/// Tarjan's algorithm without recursion. Returns the component of every node
+/// and the number of components, numbered in reverse topological order.
+fn tarjan(adj: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let n = adj.len();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = vec![];
+    let mut comp = vec![usize::MAX; n];
+    let mut count = 0;
+    let mut next = 0;
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        index[root] = next;
+        low[root] = next;
+        next += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        let mut calls = vec![(root, 0)];
+
+        while let Some((v, i)) = calls.last_mut() {
+            let v = *v;
+            if let Some(&w) = adj[v].get(*i) {
+                *i += 1;
+                if index[w] == usize::MAX {
+                    index[w] = next;
+                    low[w] = next;
+                    next += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    calls.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(u, _)) = calls.last() {
+                low[u] = low[u].min(low[v]);
+            }
+            if low[v] == index[v] {
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    comp[w] = count;
+                    if w == v {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+
+    (comp, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_placement, parse, Arrival, Beam, Direction, Field, Placement, TileType};
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    fn check(input: &str) {
+        let types = parse(input);
+        let field = Field::from(&types);
+        let all = field.energized_all();
+        assert_eq!(all.len(), 2 * (types.len() + types[0].len()));
+        for (entry, count) in all {
+            assert_eq!(count, Field::from(&types).count(entry), "{entry:?}");
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        check(EXAMPLE);
+        check(".");
+        check("/");
+        check(r"-.\|./.");
+        check("|\n.\n-\n/");
+        // Non-square, with splitters sending beams back through each other.
+        check(
+            r"..|..\.
+./.-.\.
+.|...|.
+.\.-./.
+...\../",
+        );
+        check(
+            r"\..-..|../
+.|.\./..-.
+-..../..|.
+..\|...\..",
+        );
+    }
+
+    #[test]
+    fn best_entry() {
+        let types = parse(EXAMPLE);
+        let (entry, count) = Field::from(&types).best_entry();
+        assert_eq!(count, 51);
+        assert_eq!(Field::from(&types).count(entry), 51);
+    }
+
+    #[test]
+    fn arrivals() {
+        let types = parse(EXAMPLE);
+        let field = Field::from(&types);
+        let start = Beam::from(Direction::Right, (0, -1));
+        let arrivals = field.arrivals(start);
+
+        assert_eq!(
+            field.render(&arrivals),
+            r">|<<<\....
+|v-.\^....
+.v...|->>>
+.v...v^.|.
+.v...v^...
+.v...v^..\
+.v../2\\..
+<->-/vv|..
+.|<<<2-|.\
+.v//.|.v..
+"
+        );
+        assert_eq!(
+            arrivals.iter().flatten().flatten().count(),
+            Field::from(&types).count(start)
+        );
+        assert_eq!(
+            arrivals[0][0],
+            Some(Arrival {
+                step: 1,
+                dirs: vec![Direction::Right]
+            })
+        );
+        assert_eq!(arrivals[1][1].as_ref().map(|a| a.step), Some(3));
+        assert_eq!(
+            arrivals[6][5],
+            Some(Arrival {
+                step: 14,
+                dirs: vec![Direction::Right, Direction::Bottom]
+            })
+        );
+        assert_eq!(arrivals[0][5].as_ref().map(|a| a.step), Some(28));
+        assert_eq!(arrivals[0][6], None);
+    }
+
+    /// Every placement of `budget` tiles on empty cells, without pruning.
+    fn brute_force(types: &[Vec<TileType>], start: Beam, budget: usize) -> usize {
+        let mut best = Field::from(types).count(start);
+        if budget == 0 {
+            return best;
+        }
+        let mut grid = types.to_vec();
+        for i in 0..grid.len() {
+            for j in 0..grid[0].len() {
+                if grid[i][j] != TileType::Empty {
+                    continue;
+                }
+                for tile in ['/', '\\', '-', '|'].map(TileType::from) {
+                    grid[i][j] = tile;
+                    best = best.max(brute_force(&grid, start, budget - 1));
+                    grid[i][j] = TileType::Empty;
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn extra_mirrors() {
+        let types = parse(EXAMPLE);
+        let start = Beam::from(Direction::Right, (0, -1));
+        let (placed, count) = best_placement(&types, start, 1);
+        assert_eq!(count, brute_force(&types, start, 1));
+        assert_eq!(placed.len(), 1);
+
+        let mut grid = types.clone();
+        for Placement { pos, tile } in placed {
+            assert_eq!(grid[pos.0][pos.1], TileType::Empty);
+            grid[pos.0][pos.1] = tile;
+        }
+        assert_eq!(Field::from(&grid).count(start), count);
+
+        assert_eq!(best_placement(&types, start, 0), (vec![], 46));
+    }
+
+    #[test]
+    fn two_extra_mirrors() {
+        let types = parse(
+            r".....\
+..-...
+/.....
+....|.
+..\...
+......",
+        );
+        for start in Field::from(&types).entries().into_iter().step_by(5) {
+            let (placed, count) = best_placement(&types, start, 2);
+            assert_eq!(count, brute_force(&types, start, 2), "{start:?}");
+            assert!(placed.len() <= 2);
+        }
+    }
+}
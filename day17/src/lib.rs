@@ -0,0 +1,341 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum Dir {
+    L,
+    R,
+    T,
+    B,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::L, Dir::R, Dir::T, Dir::B];
+
+    pub fn is_opposite(&self, other: Self) -> bool {
+        matches!(
+            (*self, other),
+            (Dir::L, Dir::R) | (Dir::R, Dir::L) | (Dir::T, Dir::B) | (Dir::B, Dir::T)
+        )
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir::L => '<',
+            Dir::R => '>',
+            Dir::T => '^',
+            Dir::B => 'v',
+        }
+    }
+
+    fn offset(self) -> (i32, i32) {
+        match self {
+            Dir::L => (0, -1),
+            Dir::R => (0, 1),
+            Dir::T => (-1, 0),
+            Dir::B => (1, 0),
+        }
+    }
+}
+
+/// A crucible that has just moved onto `pos`, going `dir` for the last `run` blocks.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub struct State {
+    pub pos: (i32, i32),
+    pub dir: Dir,
+    pub run: usize,
+}
+
+/// A block on the best path, the direction the crucible entered it in, and
+/// the heat lost up to and including it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Step {
+    pub pos: (i32, i32),
+    pub dir: Dir,
+    pub loss: usize,
+}
+
+pub fn parse(input: &str) -> Vec<Vec<usize>> {
+    input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|ch| ch.to_digit(10).unwrap() as usize)
+                .collect()
+        })
+        .collect()
+}
+
+pub struct Field {
+    field: Vec<Vec<usize>>,
+    width: i32,
+    height: i32,
+}
+
+impl Field {
+    pub fn from(field: Vec<Vec<usize>>) -> Self {
+        let width = field[0].len();
+        let height = field.len();
+        Field {
+            field,
+            width: width as i32,
+            height: height as i32,
+        }
+    }
+
+    fn loss(&self, (i, j): (i32, i32)) -> usize {
+        self.field[i as usize][j as usize]
+    }
+
+    fn inside(&self, (i, j): (i32, i32)) -> bool {
+        (0..self.height).contains(&i) && (0..self.width).contains(&j)
+    }
+
+    /// Position in the dense state array: `max_run` runs for each of the
+    /// four directions of every block.
+    fn index(&self, state: State, max_run: usize) -> usize {
+        let block = (state.pos.0 * self.width + state.pos.1) as usize;
+        let dir = Dir::ALL.iter().position(|dir| *dir == state.dir).unwrap();
+        (block * 4 + dir) * max_run + state.run - 1
+    }
+
+    fn state(&self, index: usize, max_run: usize) -> State {
+        let (rest, run) = (index / max_run, index % max_run + 1);
+        let (block, dir) = ((rest / 4) as i32, Dir::ALL[rest % 4]);
+        State {
+            pos: (block / self.width, block % self.width),
+            dir,
+            run,
+        }
+    }
+
+    /// Where the crucible can go next: straight on while the run is shorter
+    /// than `max_run`, or left or right once it is at least `min_run`.
+    fn moves(
+        &self,
+        state: State,
+        min_run: usize,
+        max_run: usize,
+    ) -> impl Iterator<Item = State> + '_ {
+        Dir::ALL.into_iter().filter_map(move |dir| {
+            let run = if dir == state.dir { state.run + 1 } else { 1 };
+            let turn_ok = dir == state.dir || state.run >= min_run && !dir.is_opposite(state.dir);
+            let offset = dir.offset();
+            let pos = (state.pos.0 + offset.0, state.pos.1 + offset.1);
+            (turn_ok && run <= max_run && self.inside(pos)).then_some(State { pos, dir, run })
+        })
+    }
+
+    /// Least heat loss from the top-left to the bottom-right block with a
+    /// crucible that must go between `min_run` and `max_run` blocks straight,
+    /// stopping included. `None` if it can't get there, or if no run length
+    /// fits the limits.
+    pub fn solve(&self, min_run: usize, max_run: usize) -> Option<usize> {
+        let path = self.path(min_run, max_run)?;
+        Some(path.last().map_or(0, |step| step.loss))
+    }
+
+    /// The blocks a crucible with the least heat loss moves onto, in order.
+    /// The starting block isn't included, so the path is empty if it is the goal.
+    pub fn path(&self, min_run: usize, max_run: usize) -> Option<Vec<Step>> {
+        if max_run == 0 || min_run > max_run {
+            return None;
+        }
+        let goal = (self.height - 1, self.width - 1);
+        if goal == (0, 0) {
+            return Some(vec![]);
+        }
+
+        let states = (self.width * self.height) as usize * 4 * max_run;
+        let mut dist = vec![usize::MAX; states];
+        let mut prev = vec![usize::MAX; states];
+        let mut heap = BinaryHeap::new();
+        for dir in Dir::ALL {
+            let pos = dir.offset();
+            if self.inside(pos) {
+                let index = self.index(State { pos, dir, run: 1 }, max_run);
+                dist[index] = self.loss(pos);
+                heap.push(Reverse((dist[index], index)));
+            }
+        }
+
+        while let Some(Reverse((loss, index))) = heap.pop() {
+            if loss > dist[index] {
+                continue;
+            }
+            let state = self.state(index, max_run);
+            if state.pos == goal && state.run >= min_run {
+                return Some(self.trace_back(index, &dist, &prev, max_run));
+            }
+            for next in self.moves(state, min_run, max_run) {
+                let next_loss = loss + self.loss(next.pos);
+                let next = self.index(next, max_run);
+                if next_loss < dist[next] {
+                    dist[next] = next_loss;
+                    prev[next] = index;
+                    heap.push(Reverse((next_loss, next)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Follows predecessors back to a first move. Every state on the way was
+    /// settled before its successor, so `dist` holds its final loss.
+    fn trace_back(
+        &self,
+        mut index: usize,
+        dist: &[usize],
+        prev: &[usize],
+        max_run: usize,
+    ) -> Vec<Step> {
+        let mut path = vec![];
+        while index != usize::MAX {
+            let State { pos, dir, .. } = self.state(index, max_run);
+            path.push(Step {
+                pos,
+                dir,
+                loss: dist[index],
+            });
+            index = prev[index];
+        }
+        path.reverse();
+        path
+    }
+
+    /// The map with the path drawn over it as in the puzzle, one arrow per
+    /// block pointing the way the crucible entered it.
+    pub fn render(&self, path: &[Step]) -> String {
+        let mut grid = self
+            .field
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|loss| char::from_digit(*loss as u32, 10).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for step in path {
+            grid[step.pos.0 as usize][step.pos.1 as usize] = step.dir.arrow();
+        }
+        grid.into_iter()
+            .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Dir, Field};
+
+    const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    fn solve(input: &str, min_run: usize, max_run: usize) -> Option<usize> {
+        Field::from(parse(input)).solve(min_run, max_run)
+    }
+
+    #[test]
+    fn crucibles() {
+        assert_eq!(solve(EXAMPLE, 1, 3), Some(102));
+        assert_eq!(solve(EXAMPLE, 4, 10), Some(94));
+        assert_eq!(
+            solve(
+                "111111111111\n999999999991\n999999999991\n999999999991\n999999999991",
+                4,
+                10
+            ),
+            Some(71)
+        );
+    }
+
+    #[test]
+    fn run_limits() {
+        // Must turn after every block, so it zigzags down the diagonal.
+        assert_eq!(solve("19\n11", 1, 1), Some(2));
+        assert_eq!(solve("119\n911\n991", 1, 1), Some(4));
+        // Too short to ever go straight for four blocks.
+        assert_eq!(solve("111", 4, 10), None);
+        assert_eq!(solve("11111", 4, 10), Some(4));
+        assert_eq!(solve("5", 4, 10), Some(0));
+        // No run length is allowed at all.
+        assert_eq!(solve("11\n11", 0, 0), None);
+        assert_eq!(solve("11\n11", 4, 3), None);
+        assert_eq!(solve("5", 1, 0), None);
+    }
+
+    #[test]
+    fn path() {
+        let field = Field::from(parse(EXAMPLE));
+        let path = field.path(1, 3).unwrap();
+        assert_eq!(path.last().unwrap().loss, 102);
+        assert_eq!(path.last().unwrap().pos, (12, 12));
+
+        // Each step moves one block in its direction and adds that block's loss.
+        let mut pos = (0, 0);
+        let mut loss = 0;
+        for step in &path {
+            let offset = match step.dir {
+                Dir::L => (0, -1),
+                Dir::R => (0, 1),
+                Dir::T => (-1, 0),
+                Dir::B => (1, 0),
+            };
+            pos = (pos.0 + offset.0, pos.1 + offset.1);
+            loss += field.loss(pos);
+            assert_eq!((step.pos, step.loss), (pos, loss));
+        }
+        for run in path.chunk_by(|a, b| a.dir == b.dir) {
+            assert!(run.len() <= 3);
+        }
+
+        // Loses as much as the path in the puzzle, with a different detour on the second row.
+        assert_eq!(
+            field.render(&path),
+            "2>>34^>>>1323
+32v>>>35v>623
+325524565v>54
+3446585845v52
+4546657867v>6
+14385987984v4
+44578769877v6
+36378779796v>
+465496798688v
+456467998645v
+12246868655<v
+25465488877v5
+43226746555v>
+"
+        );
+    }
+
+    #[test]
+    fn render() {
+        let field = Field::from(parse(
+            "111111111111\n999999999991\n999999999991\n999999999991\n999999999991",
+        ));
+        let path = field.path(4, 10).unwrap();
+        // The top row is one block too long to take in a single run.
+        assert_eq!(
+            field.render(&path),
+            "1>>>>>>>1111
+9999999v9991
+9999999v9991
+9999999v9991
+9999999v>>>>
+"
+        );
+        assert!(Field::from(parse("7")).path(4, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+use day7::Rules;
+
+const USAGE: &str = "usage: camel <rules> [input] [--explain]";
+
+fn main() {
+    let mut explain = false;
+    let mut paths = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--explain" => explain = true,
+            _ => paths.push(arg),
+        }
+    }
+
+    let (rules, input) = match paths.as_slice() {
+        [rules] => (rules, "input1.txt"),
+        [rules, input] => (rules, input.as_str()),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(1);
+        }
+    };
+
+    let rules = std::fs::read_to_string(rules).unwrap();
+    let rules = Rules::parse(&rules).unwrap_or_else(|err| {
+        eprintln!("bad rules: {err}");
+        std::process::exit(1);
+    });
+    let input = std::fs::read_to_string(input).unwrap();
+
+    let res = if explain {
+        day7::explain(&input, &rules).map(|text| print!("{text}"))
+    } else {
+        Ok(())
+    }
+    .and_then(|()| day7::solution(&input, &rules));
+    let res = res.unwrap_or_else(|err| {
+        eprintln!("bad input: {err}");
+        std::process::exit(1);
+    });
+    println!("{res}");
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Category {
+    pub name: String,
+    pub groups: Vec<usize>,
+}
+
+impl Category {
+    /// Checks whether the wild cards can fill every group the category asks for.
+    /// Both `counts` and `groups` are sorted from biggest to smallest, so pairing
+    /// them up in order needs the fewest wild cards.
+    fn fits(&self, counts: &[usize], wild: usize) -> bool {
+        let missing: usize = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(i, group)| group.saturating_sub(counts.get(i).copied().unwrap_or(0)))
+            .sum();
+        missing <= wild
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rules {
+    /// Card alphabet from weakest to strongest.
+    pub cards: Vec<char>,
+    pub wild: Vec<char>,
+    /// Hand categories from strongest to weakest.
+    pub categories: Vec<Category>,
+}
+
+impl Rules {
+    pub fn part1() -> Self {
+        Self::parse(include_str!("../rules/part1.txt")).unwrap()
+    }
+
+    pub fn part2() -> Self {
+        Self::parse(include_str!("../rules/part2.txt")).unwrap()
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut cards = Vec::new();
+        let mut wild = Vec::new();
+        let mut categories = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            let Some((key, rest)) = line.split_once(char::is_whitespace) else {
+                if line.is_empty() {
+                    continue;
+                }
+                return Err(format!("line {}: `{line}` has no value", i + 1));
+            };
+            let rest = rest.trim();
+
+            match key {
+                "cards" => cards = rest.chars().filter(|ch| !ch.is_whitespace()).collect(),
+                "wild" => wild = rest.chars().filter(|ch| !ch.is_whitespace()).collect(),
+                "category" => {
+                    let mut words = rest.split_whitespace();
+                    let name = words.next().unwrap().to_string();
+                    let mut groups = words
+                        .map(|word| word.parse::<usize>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|err| format!("line {}: {err}", i + 1))?;
+                    groups.sort_unstable_by(|a, b| b.cmp(a));
+                    categories.push(Category { name, groups });
+                }
+                _ => return Err(format!("line {}: unknown key `{key}`", i + 1)),
+            }
+        }
+
+        if cards.is_empty() {
+            return Err("no cards defined".to_string());
+        }
+        if cards.len() > u8::MAX as usize + 1 {
+            return Err(format!("{} cards defined, at most 256 fit", cards.len()));
+        }
+        for (i, ch) in cards.iter().enumerate() {
+            if let Some(j) = cards[..i].iter().position(|c| c == ch) {
+                return Err(format!(
+                    "card `{ch}` is listed twice, at {} and {}",
+                    j + 1,
+                    i + 1
+                ));
+            }
+        }
+        if let Some(ch) = wild.iter().find(|ch| !cards.contains(ch)) {
+            return Err(format!("wild card `{ch}` is not in the card list"));
+        }
+        // Any hand with a card has a group of one, so the weakest category
+        // may ask for at most that.
+        match categories.last().map(|last| last.groups.as_slice()) {
+            Some([] | [0 | 1]) => {}
+            _ => return Err("the weakest category must match every hand".to_string()),
+        }
+
+        Ok(Self {
+            cards,
+            wild,
+            categories,
+        })
+    }
+
+    fn rank(&self, value: char) -> Result<u8, String> {
+        match self.cards.iter().position(|&ch| ch == value) {
+            Some(rank) => Ok(rank as u8),
+            None => Err(format!("card `{value}` is not in the ruleset")),
+        }
+    }
+
+    /// Index of the strongest category the hand fits into.
+    pub fn classify(&self, inp: &str) -> Result<usize, String> {
+        let mut counts = HashMap::new();
+        let mut wild = 0;
+        for ch in inp.chars() {
+            if self.wild.contains(&ch) {
+                wild += 1;
+            } else {
+                *counts.entry(ch).or_insert(0) += 1;
+            }
+        }
+        let mut counts = counts.into_values().collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        self.categories
+            .iter()
+            .position(|category| category.fits(&counts, wild))
+            .ok_or_else(|| "no category matches".to_string())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Kard(u8);
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Hand {
+    strength: usize,
+    comb: Vec<Kard>,
+}
+
+impl Hand {
+    pub fn new(inp: &str, rules: &Rules) -> Result<Self, String> {
+        let comb = inp
+            .chars()
+            .map(|ch| rules.rank(ch).map(Kard))
+            .collect::<Result<_, _>>()?;
+        let strength = rules.categories.len() - rules.classify(inp)?;
+        Ok(Self { strength, comb })
+    }
+
+    pub fn category<'a>(&self, rules: &'a Rules) -> &'a Category {
+        &rules.categories[rules.categories.len() - self.strength]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Play<'a> {
+    pub cards: &'a str,
+    pub hand: Hand,
+    pub bid: u128,
+    pub rank: usize,
+}
+
+/// Parses and ranks every hand; the result keeps the input order.
+pub fn play<'a>(input: &'a str, rules: &Rules) -> Result<Vec<Play<'a>>, String> {
+    let mut plays = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (cards, bid) = line
+                .split_once(' ')
+                .ok_or(format!("line {}: no bid", i + 1))?;
+            if cards.is_empty() {
+                return Err(format!("line {}: empty hand", i + 1));
+            }
+            Ok(Play {
+                cards,
+                hand: Hand::new(cards, rules).map_err(|err| format!("line {}: {err}", i + 1))?,
+                bid: bid
+                    .trim()
+                    .parse()
+                    .map_err(|err| format!("line {}: {err}", i + 1))?,
+                rank: 0,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut order = (0..plays.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| plays[a].hand.cmp(&plays[b].hand));
+    for (rank, i) in order.into_iter().enumerate() {
+        plays[i].rank = rank + 1;
+    }
+
+    Ok(plays)
+}
+
+pub fn explain(input: &str, rules: &Rules) -> Result<String, String> {
+    Ok(play(input, rules)?
+        .iter()
+        .map(|play| {
+            format!(
+                "{} {:<12} rank {:>4} bid {}\n",
+                play.cards,
+                play.hand.category(rules).name,
+                play.rank,
+                play.bid
+            )
+        })
+        .collect())
+}
+
+pub fn solution(input: &str, rules: &Rules) -> Result<u128, String> {
+    Ok(play(input, rules)?
+        .iter()
+        .map(|play| play.rank as u128 * play.bid)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{explain, solution, Hand, Rules};
+
+    fn category(inp: &str, rules: &Rules) -> String {
+        Hand::new(inp, rules).unwrap().category(rules).name.clone()
+    }
+
+    #[test]
+    fn jokers_join_biggest_group() {
+        let (part1, part2) = (Rules::part1(), Rules::part2());
+        assert_eq!(category("JJJJJ", &part2), "FiveOfKind");
+        assert_eq!(category("KTJJT", &part2), "FourOfKind");
+        assert_eq!(category("KTJJT", &part1), "TwoPairs");
+        assert_eq!(category("2345J", &part2), "OnePair");
+        assert_eq!(category("22J33", &part2), "FullHouse");
+    }
+
+    #[test]
+    fn other_hand_sizes() {
+        let (part1, part2) = (Rules::part1(), Rules::part2());
+        assert_eq!(category("AAK", &part1), "OnePair");
+        assert_eq!(category("AAKK", &part1), "TwoPairs");
+        assert_eq!(category("AAAKKK7", &part1), "FullHouse");
+        assert_eq!(category("AAAAJJ", &part2), "FiveOfKind");
+        assert_eq!(category("23456789", &part1), "HighKard");
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = Rules::parse(
+            "cards 1234
+wild 1
+category Flush 4
+category Pair 2
+category Nothing 1",
+        )
+        .unwrap();
+        assert_eq!(category("1234", &rules), "Pair");
+        assert_eq!(category("2233", &rules), "Pair");
+        assert_eq!(category("1133", &rules), "Flush");
+        assert_eq!(solution("2233 10\n4321 1\n3332 100", &rules), Ok(213));
+    }
+
+    #[test]
+    fn bad_rules() {
+        assert!(Rules::parse("cards 123\nwild 4\ncategory Any 1").is_err());
+        assert!(Rules::parse("cards 123\ncategory Pair 2").is_err());
+        assert!(Rules::parse("cards 123\ncolour red\ncategory Any 1").is_err());
+        assert!(Rules::parse("cards 1231\ncategory Any 1").is_err());
+        assert!(Rules::parse("cards 123\ncategory Nothing 1 1").is_err());
+        let many = (0x100..0x300)
+            .filter_map(char::from_u32)
+            .collect::<String>();
+        assert!(Rules::parse(&format!("cards {many}\ncategory Any 1"))
+            .unwrap_err()
+            .contains("at most 256"));
+    }
+
+    #[test]
+    fn bad_hands() {
+        let rules = Rules::part1();
+        assert_eq!(
+            solution("32T3K 765\nZ2345 1", &rules),
+            Err("line 2: card `Z` is not in the ruleset".to_string())
+        );
+        assert!(solution("32T3K", &rules).is_err());
+        assert!(solution("32T3K x", &rules).is_err());
+        assert_eq!(
+            solution("32T3K 765\n 5", &rules),
+            Err("line 2: empty hand".to_string())
+        );
+        assert_eq!(
+            Hand::new("", &rules),
+            Err("no category matches".to_string())
+        );
+    }
+
+    #[test]
+    fn explain_ranks() {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        let res = explain(input, &Rules::part2()).unwrap();
+        assert_eq!(
+            res,
+            "32T3K OnePair      rank    1 bid 765
+T55J5 FourOfKind   rank    3 bid 684
+KK677 TwoPairs     rank    2 bid 28
+KTJJT FourOfKind   rank    5 bid 220
+QQQJA FourOfKind   rank    4 bid 483
+"
+        );
+    }
+}
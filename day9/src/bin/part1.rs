@@ -1,23 +1,5 @@
-use itertools::*;
-
-fn solution(input: &str) -> i64 {
-    input
-        .lines()
-        .map(|line| {
-            let mut arr = line
-                .split_whitespace()
-                .map(|n| n.parse::<i64>().unwrap())
-                .collect::<Vec<_>>();
-            let mut finals = vec![];
-
-            while arr.iter().any(|n| *n != 0) {
-                finals.push(*arr.last().unwrap());
-                arr = arr.iter().tuple_windows().map(|(a, b)| b - a).collect();
-            }
-
-            finals.into_iter().sum::<i64>()
-        })
-        .sum()
+fn solution(input: &str) -> i128 {
+    day9::solution(input, |len| len as i128).unwrap()
 }
 
 fn main() {
@@ -1,24 +1,5 @@
-use itertools::*;
-
-fn solution(input: &str) -> i64 {
-    input
-        .lines()
-        .map(|line| {
-            let mut arr = line
-                .split_whitespace()
-                .map(|n| n.parse::<i64>().unwrap())
-                .collect::<Vec<_>>();
-            let mut firsts = vec![];
-
-            while arr.iter().any(|n| *n != 0) {
-                firsts.push(*arr.first().unwrap());
-                arr = arr.iter().tuple_windows().map(|(a, b)| b - a).collect();
-            }
-
-            firsts.reverse();
-            firsts.into_iter().fold(0, |acc, elem| elem - acc)
-        })
-        .sum()
+fn solution(input: &str) -> i128 {
+    day9::solution(input, |_| -1).unwrap()
 }
 
 fn main() {
@@ -0,0 +1,134 @@
+use itertools::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// Differences ran out of values before turning all zero.
+    NotPolynomial,
+    Overflow,
+}
+
+/// The minimal-degree polynomial through a sequence, kept in Newton forward form:
+/// `value(x) = sum of binomial(x, k) * diffs[k]`, where `diffs[k]` is the first
+/// value of the k-th difference row.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Sequence {
+    diffs: Vec<i128>,
+    len: usize,
+}
+
+impl Sequence {
+    pub fn fit(values: &[i64]) -> Result<Self, Error> {
+        let mut arr = values.iter().map(|&n| n as i128).collect::<Vec<_>>();
+        let mut diffs = vec![];
+
+        while arr.iter().any(|n| *n != 0) {
+            diffs.push(arr[0]);
+            arr = arr
+                .iter()
+                .tuple_windows()
+                .map(|(a, b)| b.checked_sub(*a).ok_or(Error::Overflow))
+                .collect::<Result<_, _>>()?;
+        }
+        if arr.is_empty() {
+            return Err(Error::NotPolynomial);
+        }
+
+        Ok(Self {
+            diffs,
+            len: values.len(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Degree of the polynomial; an all-zero sequence counts as degree 0.
+    pub fn degree(&self) -> usize {
+        self.diffs.len().saturating_sub(1)
+    }
+
+    /// Value at index `x`: `0..len` are the fitted values, `len` is the next one
+    /// and `-1` is the one before the first.
+    pub fn at(&self, x: i128) -> Result<i128, Error> {
+        let mut binomial = 1i128;
+        let mut res = 0i128;
+        for (k, diff) in self.diffs.iter().enumerate() {
+            let k = k as i128;
+            if k > 0 {
+                // binomial(x, k) = binomial(x, k - 1) * (x - k + 1) / k, and the division is exact.
+                binomial = binomial
+                    .checked_mul(x - k + 1)
+                    .ok_or(Error::Overflow)?
+                    / k;
+            }
+            res = binomial
+                .checked_mul(*diff)
+                .and_then(|term| res.checked_add(term))
+                .ok_or(Error::Overflow)?;
+        }
+        Ok(res)
+    }
+}
+
+pub fn parse(input: &str) -> Vec<Vec<i64>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_whitespace()
+                .map(|n| n.parse::<i64>().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// Sums the value of every sequence at the index picked from its length.
+pub fn solution(input: &str, index: impl Fn(usize) -> i128) -> Result<i128, Error> {
+    parse(input)
+        .iter()
+        .map(|values| {
+            let seq = Sequence::fit(values)?;
+            seq.at(index(seq.len()))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Sequence};
+
+    #[test]
+    fn degree() {
+        assert_eq!(Sequence::fit(&[0, 0, 0]).unwrap().degree(), 0);
+        assert_eq!(Sequence::fit(&[7, 7, 7]).unwrap().degree(), 0);
+        assert_eq!(Sequence::fit(&[0, 3, 6, 9, 12, 15]).unwrap().degree(), 1);
+        assert_eq!(Sequence::fit(&[10, 13, 16, 21, 30, 45]).unwrap().degree(), 3);
+    }
+
+    #[test]
+    fn any_offset() {
+        let seq = Sequence::fit(&[1, 4, 9, 16]).unwrap();
+        assert_eq!(seq.degree(), 2);
+        for x in -50..50 {
+            assert_eq!(seq.at(x), Ok((x + 1) * (x + 1)));
+        }
+        assert_eq!(seq.at(999_999), Ok(1_000_000_000_000));
+    }
+
+    #[test]
+    fn not_polynomial() {
+        assert_eq!(Sequence::fit(&[1, 2, 4, 8]), Err(Error::NotPolynomial));
+        assert_eq!(Sequence::fit(&[5]), Err(Error::NotPolynomial));
+    }
+
+    #[test]
+    fn overflow() {
+        let seq = Sequence::fit(&[0, 1, 8, 27, 64]).unwrap();
+        assert_eq!(seq.at(i64::MAX as i128), Err(Error::Overflow));
+    }
+}
@@ -0,0 +1,25 @@
+use nonogram::{render, solve, Clues, Solution};
+
+fn main() {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: solve <puzzle.non>");
+        std::process::exit(1);
+    };
+
+    let input = std::fs::read_to_string(path).unwrap();
+    let clues = Clues::parse(&input).unwrap_or_else(|err| {
+        eprintln!("bad clues: {err}");
+        std::process::exit(1);
+    });
+
+    match solve(&clues) {
+        Solution::None => println!("no solution"),
+        Solution::Unique(grid) => print!("{}", render(&grid)),
+        Solution::Multiple(a, b) => {
+            println!("multiple solutions, for example:");
+            print!("{}", render(&a));
+            println!();
+            print!("{}", render(&b));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use day12::{Arrangements, Row, Spring};
+
+/// Run lengths for every row (top to bottom) and column (left to right).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Clues {
+    pub rows: Vec<Vec<usize>>,
+    pub cols: Vec<Vec<usize>>,
+}
+
+impl Clues {
+    /// Reads the `.non` format: `rows` and `columns` sections with one clue per
+    /// line, numbers separated by commas or spaces and `0` for an empty line.
+    /// `width` and `height` are checked when present; other keys such as
+    /// `title` are ignored.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut rows = vec![];
+        let mut cols = vec![];
+        let mut width = None;
+        let mut height = None;
+        let mut section = None;
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let key = words.next().unwrap();
+
+            match key {
+                "rows" => section = Some(&mut rows),
+                "columns" => section = Some(&mut cols),
+                "width" | "height" => {
+                    let value = words
+                        .next()
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .ok_or(format!("line {}: bad {key}", i + 1))?;
+                    match key {
+                        "width" => width = Some(value),
+                        _ => height = Some(value),
+                    }
+                }
+                _ if key.starts_with(|ch: char| ch.is_ascii_digit()) => {
+                    let Some(section) = section.as_mut() else {
+                        return Err(format!("line {}: clue outside of rows or columns", i + 1));
+                    };
+                    let clue = line
+                        .split([',', ' '])
+                        .filter(|num| !num.is_empty())
+                        .map(|num| num.parse::<usize>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|err| format!("line {}: {err}", i + 1))?;
+                    section.push(clue.into_iter().filter(|num| *num > 0).collect());
+                }
+                _ => section = None,
+            }
+        }
+
+        if width.is_some_and(|width| width != cols.len()) {
+            return Err(format!(
+                "width is {} but there are {} columns",
+                width.unwrap(),
+                cols.len()
+            ));
+        }
+        if height.is_some_and(|height| height != rows.len()) {
+            return Err(format!(
+                "height is {} but there are {} rows",
+                height.unwrap(),
+                rows.len()
+            ));
+        }
+        if rows.is_empty() || cols.is_empty() {
+            return Err("no rows or no columns".to_string());
+        }
+
+        Ok(Self { rows, cols })
+    }
+}
+
+pub type Grid = Vec<Vec<Spring>>;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Solution {
+    None,
+    Unique(Grid),
+    /// Two different solutions; there may be more.
+    Multiple(Grid, Grid),
+}
+
+/// Narrows one line down to the cells every arrangement agrees on. Returns
+/// `None` on a contradiction and whether anything changed otherwise.
+fn narrow(line: &mut [Spring], nums: &[usize]) -> Option<bool> {
+    let row = Row {
+        springs: line.to_vec(),
+        nums: nums.to_vec(),
+    };
+    let forced = Arrangements::new(&row).forced()?;
+
+    let changed = line != forced.as_slice();
+    line.copy_from_slice(&forced);
+    Some(changed)
+}
+
+/// Applies the row and column constraints until nothing changes.
+/// Returns `None` if some line has no arrangement left.
+fn propagate(clues: &Clues, grid: &mut Grid) -> Option<()> {
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for (line, nums) in grid.iter_mut().zip(&clues.rows) {
+            changed |= narrow(line, nums)?;
+        }
+
+        for (col, nums) in clues.cols.iter().enumerate() {
+            let mut line = grid.iter().map(|row| row[col]).collect::<Vec<_>>();
+            if narrow(&mut line, nums)? {
+                changed = true;
+                for (row, cell) in grid.iter_mut().zip(line) {
+                    row[col] = cell;
+                }
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// Propagates, then guesses the first unknown cell and recurses on both options.
+/// Stops once `limit` solutions are found.
+fn search(clues: &Clues, mut grid: Grid, found: &mut Vec<Grid>, limit: usize) {
+    if propagate(clues, &mut grid).is_none() {
+        return;
+    }
+
+    let unknown = grid.iter().enumerate().find_map(|(i, row)| {
+        row.iter()
+            .position(|cell| *cell == Spring::Unknown)
+            .map(|j| (i, j))
+    });
+    let Some((i, j)) = unknown else {
+        found.push(grid);
+        return;
+    };
+
+    for guess in [Spring::Damaged, Spring::Operational] {
+        if found.len() >= limit {
+            return;
+        }
+        let mut next = grid.clone();
+        next[i][j] = guess;
+        search(clues, next, found, limit);
+    }
+}
+
+pub fn solve(clues: &Clues) -> Solution {
+    let grid = vec![vec![Spring::Unknown; clues.cols.len()]; clues.rows.len()];
+    let mut found = vec![];
+    search(clues, grid, &mut found, 2);
+
+    let mut found = found.into_iter();
+    match (found.next(), found.next()) {
+        (None, _) => Solution::None,
+        (Some(grid), None) => Solution::Unique(grid),
+        (Some(a), Some(b)) => Solution::Multiple(a, b),
+    }
+}
+
+/// Filled cells as `#` and empty ones as `.`, one row per line.
+pub fn render(grid: &Grid) -> String {
+    grid.iter().map(|row| day12::render(row) + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, solve, Clues, Solution};
+
+    fn unique(clues: &str) -> String {
+        match solve(&Clues::parse(clues).unwrap()) {
+            Solution::Unique(grid) => render(&grid),
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn solves_by_propagation() {
+        let res = unique(include_str!("../puzzles/letter_a.non"));
+        assert_eq!(
+            res,
+            ".###.
+#...#
+#####
+#...#
+#...#
+"
+        );
+    }
+
+    #[test]
+    fn solves_duck() {
+        let res = unique(include_str!("../puzzles/duck.non"));
+        assert_eq!(
+            res,
+            "....###...
+...##.#...
+..###.##..
+.##...##..
+.######...
+.#.#####..
+..######..
+...#......
+...##.....
+..........
+"
+        );
+    }
+
+    #[test]
+    fn needs_backtracking() {
+        // Line by line, only one cell of this puzzle can be settled.
+        let res = unique("rows\n1\n2\n1\n1,1\n2,1\ncolumns\n1\n1,1\n1\n3\n1,1");
+        assert_eq!(res, ".#...\n...##\n...#.\n#..#.\n.##.#\n");
+    }
+
+    #[test]
+    fn multiple_solutions() {
+        let clues = Clues::parse("rows\n1\n1\ncolumns\n1\n1").unwrap();
+        let Solution::Multiple(a, b) = solve(&clues) else {
+            panic!();
+        };
+        assert_ne!(a, b);
+        assert_eq!(render(&a), "#.\n.#\n");
+        assert_eq!(render(&b), ".#\n#.\n");
+    }
+
+    #[test]
+    fn no_solution() {
+        let clues = Clues::parse("rows\n2\n0\ncolumns\n0\n0").unwrap();
+        assert_eq!(solve(&clues), Solution::None);
+    }
+
+    #[test]
+    fn bad_files() {
+        assert!(Clues::parse("width 3\nrows\n1\ncolumns\n1").is_err());
+        assert!(Clues::parse("rows\n1,x\ncolumns\n1").is_err());
+        assert!(Clues::parse("1\nrows\n1\ncolumns\n1").is_err());
+    }
+}